@@ -0,0 +1,184 @@
+//! RPC interface for the slashing voting pallet
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_slashing_voting::types::{ArchivedProposal, Phase, Proposal, VoterBalance};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_slashing_voting_runtime_api::SlashingVotingApi as SlashingVotingRuntimeApi;
+
+/// Queries the proposals, members and tallies of the committee
+#[rpc(client, server)]
+pub trait SlashingVotingApi<BlockHash, AccountId, BlockNumber, Balance, Hash> {
+	/// The data and the running tally of an active proposal
+	#[method(name = "voting_proposal")]
+	fn proposal(
+		&self,
+		hash: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Proposal<AccountId, BlockNumber, Balance>>>;
+
+	/// The hashes of the proposals in commit or reveal phase
+	#[method(name = "voting_activeProposals")]
+	fn active_proposals(&self, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+
+	/// The archived results of a closed proposal
+	#[method(name = "voting_archived")]
+	fn archived(
+		&self,
+		hash: Hash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ArchivedProposal<AccountId, BlockNumber, Balance>>>;
+
+	/// The stake and voting tokens of a member
+	#[method(name = "voting_member")]
+	fn member(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<VoterBalance<Balance>>>;
+
+	/// The phase of a proposal
+	#[method(name = "voting_phase")]
+	fn phase(&self, hash: Hash, at: Option<BlockHash>) -> RpcResult<Option<Phase<BlockNumber>>>;
+
+	/// The proposals the member has committed to and not revealed yet
+	#[method(name = "voting_pendingReveals")]
+	fn pending_reveals(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+
+	/// The block until which the stake of the member is locked by conviction votes
+	#[method(name = "voting_lockedUntil")]
+	fn locked_until(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BlockNumber>>;
+
+	/// The closed proposals the member has votes or rewards to claim on
+	#[method(name = "voting_pendingClaims")]
+	fn pending_claims(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+}
+
+/// Provides the RPC methods to query the state of the committee
+pub struct SlashingVoting<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> SlashingVoting<C, Block> {
+	/// Creates a new instance of the SlashingVoting RPC handler
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The error code returned when the runtime API call fails
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(message: &str, error: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{:?}", error))))
+		.into()
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance, Hash>
+	SlashingVotingApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance, Hash>
+	for SlashingVoting<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: SlashingVotingRuntimeApi<Block, AccountId, BlockNumber, Balance, Hash>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Hash: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn proposal(
+		&self,
+		hash: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Proposal<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.proposal(&at, hash).map_err(|e| runtime_error("Unable to query proposal.", e))
+	}
+
+	fn active_proposals(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.active_proposals(&at)
+			.map_err(|e| runtime_error("Unable to query active proposals.", e))
+	}
+
+	fn archived(
+		&self,
+		hash: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ArchivedProposal<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.archived(&at, hash)
+			.map_err(|e| runtime_error("Unable to query archived proposal.", e))
+	}
+
+	fn member(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<VoterBalance<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.member(&at, account).map_err(|e| runtime_error("Unable to query member.", e))
+	}
+
+	fn phase(
+		&self,
+		hash: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Phase<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.phase(&at, hash).map_err(|e| runtime_error("Unable to query phase.", e))
+	}
+
+	fn pending_reveals(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.pending_reveals(&at, account)
+			.map_err(|e| runtime_error("Unable to query pending reveals.", e))
+	}
+
+	fn locked_until(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.locked_until(&at, account)
+			.map_err(|e| runtime_error("Unable to query conviction lock.", e))
+	}
+
+	fn pending_claims(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.pending_claims(&at, account)
+			.map_err(|e| runtime_error("Unable to query pending claims.", e))
+	}
+}
@@ -0,0 +1,35 @@
+//! Runtime API definition for the slashing voting pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_slashing_voting::types::{ArchivedProposal, Phase, Proposal, VoterBalance};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Queries the proposals, members and tallies of the committee
+	pub trait SlashingVotingApi<AccountId, BlockNumber, Balance, Hash>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+		Hash: Codec,
+	{
+		/// The data and the running tally of an active proposal
+		fn proposal(hash: Hash) -> Option<Proposal<AccountId, BlockNumber, Balance>>;
+		/// The hashes of the proposals in commit or reveal phase
+		fn active_proposals() -> Vec<Hash>;
+		/// The archived results of a closed proposal
+		fn archived(hash: Hash) -> Vec<ArchivedProposal<AccountId, BlockNumber, Balance>>;
+		/// The stake and voting tokens of a member, `None` if the account is not a member
+		fn member(account: AccountId) -> Option<VoterBalance<Balance>>;
+		/// The phase of a proposal, `None` if the proposal is unknown
+		fn phase(hash: Hash) -> Option<Phase<BlockNumber>>;
+		/// The proposals the member has committed to and not revealed yet
+		fn pending_reveals(account: AccountId) -> Vec<Hash>;
+		/// The block until which the stake of the member is locked by conviction votes
+		fn locked_until(account: AccountId) -> Option<BlockNumber>;
+		/// The closed proposals the member has votes or rewards to claim on
+		fn pending_claims(account: AccountId) -> Vec<Hash>;
+	}
+}
@@ -0,0 +1,386 @@
+//! Benchmarks of the pallet
+
+use super::*;
+use crate::types::{Conviction, Data, MaxOptions, Options, TrackId};
+use sp_runtime::Perbill;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, Get},
+};
+use frame_system::RawOrigin as SystemOrigin;
+use sp_std::boxed::Box;
+
+const SEED: u32 = 0;
+const SALT: [u8; 32] = [7u8; 32];
+
+fn funded<T: Config>(who: &T::AccountId) {
+	let balance = T::BasicDeposit::get().saturating_mul(100u32.into());
+	T::Currency::make_free_balance_be(who, balance);
+	T::IdentityProvider::set_identity(who);
+}
+
+fn member<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	funded::<T>(&who);
+	assert_ok!(Pallet::<T>::join_committee(SystemOrigin::Signed(who.clone()).into()));
+	who
+}
+
+/// Makes a new member delegate to the given one on every track
+fn delegator<T: Config>(index: u32, to: &T::AccountId) -> T::AccountId {
+	let who = member::<T>("delegator", index);
+	assert_ok!(Pallet::<T>::delegate(SystemOrigin::Signed(who.clone()).into(), to.clone(), None));
+	who
+}
+
+fn text(index: u32) -> Data {
+	Data::Raw(index.encode().try_into().unwrap())
+}
+
+/// The first track of the runtime, benchmarked proposals follow it
+fn track<T: Config>() -> (TrackId, TrackOf<T>) {
+	T::Tracks::get().into_iter().next().expect("the runtime defines a track")
+}
+
+/// The number of proposals the benchmarked track can hold
+fn max_proposals<T: Config>() -> u32 {
+	T::MaxProposals::get().min(track::<T>().1.max_proposals)
+}
+
+/// Creates a proposal in commit phase and returns its hash
+fn proposal<T: Config>(proposer: &T::AccountId, index: u32) -> T::Hash {
+	let (track, info) = track::<T>();
+	assert_ok!(Pallet::<T>::create_proposal(
+		SystemOrigin::Signed(proposer.clone()).into(),
+		Box::new(text(index)),
+		track,
+		info.thresholds[0],
+		info.min_length,
+	));
+	*<Proposals<T>>::get().last().unwrap()
+}
+
+fn commit<T: Config>(who: &T::AccountId, proposal: T::Hash, vote: Vote) {
+	let commitment = Pallet::<T>::commitment_of(who, &proposal, &vote, 1, &SALT);
+	assert_ok!(Pallet::<T>::commit_vote(
+		SystemOrigin::Signed(who.clone()).into(),
+		proposal,
+		commitment,
+		1,
+		// the closing benchmarks pay for the conviction locks
+		Conviction::Locked1x
+	));
+}
+
+fn reveal<T: Config>(who: &T::AccountId, proposal: T::Hash, vote: Vote) {
+	assert_ok!(Pallet::<T>::reveal_vote(
+		SystemOrigin::Signed(who.clone()).into(),
+		proposal,
+		vote,
+		SALT
+	));
+}
+
+/// Moves the proposal to its reveal phase
+fn close_vote<T: Config>(proposal: T::Hash) {
+	let poll_end = <ProposalData<T>>::get(proposal).unwrap().poll_end;
+	frame_system::Pallet::<T>::set_block_number(poll_end);
+	assert_ok!(Pallet::<T>::do_close_vote(proposal, None));
+}
+
+fn fund_pot<T: Config>() {
+	let pot = Pallet::<T>::account_id();
+	T::Currency::make_free_balance_be(&pot, T::Currency::minimum_balance());
+}
+
+benchmarks! {
+	join_committee {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+	}: _(SystemOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Pallet::<T>::is_member(&caller));
+	}
+
+	leave_committee {
+		let caller = member::<T>("caller", 0);
+		// an expired lock is cleared on the way out
+		<ConvictionLocks<T>>::insert(&caller, frame_system::Pallet::<T>::block_number());
+	}: _(SystemOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Pallet::<T>::is_member(&caller));
+	}
+
+	create_proposal {
+		let p in 0 .. max_proposals::<T>() - 1;
+		let caller = member::<T>("caller", 0);
+		let (track, info) = track::<T>();
+		for i in 0 .. p {
+			proposal::<T>(&caller, i);
+		}
+	}: _(SystemOrigin::Signed(caller), Box::new(text(p)), track, info.thresholds[0], info.min_length)
+	verify {
+		assert_eq!(<Proposals<T>>::get().len() as u32, p + 1);
+	}
+
+	create_call_proposal {
+		let p in 0 .. max_proposals::<T>() - 1;
+		let caller = member::<T>("caller", 0);
+		let (track, info) = track::<T>();
+		for i in 0 .. p {
+			proposal::<T>(&caller, i);
+		}
+		let call: <T as Config>::Proposal = frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+	}: _(SystemOrigin::Signed(caller), Box::new(text(p)), Box::new(call), track, info.thresholds[0], info.min_length)
+	verify {
+		assert_eq!(<Proposals<T>>::get().len() as u32, p + 1);
+	}
+
+	create_multi_option_proposal {
+		let p in 0 .. max_proposals::<T>() - 1;
+		let caller = member::<T>("caller", 0);
+		let (track, info) = track::<T>();
+		for i in 0 .. p {
+			proposal::<T>(&caller, i);
+		}
+		let options: Options = (0 .. MaxOptions::get())
+			.map(text)
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+	}: _(SystemOrigin::Signed(caller), Box::new(text(p)), options, track, info.thresholds[0], info.min_length)
+	verify {
+		assert_eq!(<Proposals<T>>::get().len() as u32, p + 1);
+	}
+
+	commit_vote {
+		// the caller is one more voter
+		let c in 0 .. T::MaxVotersPerProposal::get() - 1;
+		// delegators the commit carries votes for
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&caller, 0);
+		for i in 0 .. c {
+			commit::<T>(&member::<T>("voter", i), hash, Vote::Yes);
+		}
+		for i in 0 .. d {
+			delegator::<T>(i, &caller);
+		}
+		let commitment = Pallet::<T>::commitment_of(&caller, &hash, &Vote::Yes, 1, &SALT);
+	}: _(SystemOrigin::Signed(caller.clone()), hash, commitment, 1, Conviction::Locked8x)
+	verify {
+		assert!(<Commits<T>>::contains_key(&caller, &hash));
+	}
+
+	reveal_vote {
+		// the caller is one more voter
+		let r in 0 .. T::MaxVotersPerProposal::get() - 1;
+		// delegators the commit carries votes for
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&caller, 0);
+		let voters: Vec<T::AccountId> = (0 .. r).map(|i| member::<T>("voter", i)).collect();
+		for voter in voters.iter() {
+			commit::<T>(voter, hash, Vote::Yes);
+		}
+		for i in 0 .. d {
+			delegator::<T>(i, &caller);
+		}
+		commit::<T>(&caller, hash, Vote::Yes);
+		close_vote::<T>(hash);
+		for voter in voters.iter() {
+			reveal::<T>(voter, hash, Vote::Yes);
+		}
+	}: _(SystemOrigin::Signed(caller.clone()), hash, Vote::Yes, SALT)
+	verify {
+		assert!(<ProposalData<T>>::get(&hash).unwrap().revealed.contains(&caller));
+	}
+
+	close_vote {
+		// eligible members who committed, counted in the turnout
+		let t in 0 .. T::MaxVotersPerProposal::get();
+		let proposer = member::<T>("proposer", 0);
+		let voters: Vec<T::AccountId> = (0 .. t).map(|i| member::<T>("voter", i)).collect();
+		let hash = proposal::<T>(&proposer, 0);
+		for voter in voters.iter() {
+			commit::<T>(voter, hash, Vote::Yes);
+		}
+		let poll_end = <ProposalData<T>>::get(hash).unwrap().poll_end;
+		frame_system::Pallet::<T>::set_block_number(poll_end);
+		//anyone can close a due phase
+		let caller: T::AccountId = account("keeper", 0, SEED);
+	}: _(SystemOrigin::Signed(caller), hash)
+	verify {
+		assert!(<ProposalData<T>>::get(&hash).unwrap().reveal_end.is_some());
+	}
+
+	close_reveal {
+		// voters who revealed, every other one is on the losing side
+		let r in 0 .. T::MaxVotersPerProposal::get();
+		fund_pot::<T>();
+		let proposer = member::<T>("proposer", 0);
+		let hash = proposal::<T>(&proposer, 0);
+		let revealing: Vec<(T::AccountId, Vote)> = (0 .. r)
+			.map(|i| {
+				let vote = if i % 2 == 0 { Vote::Yes } else { Vote::No };
+				(member::<T>("revealing", i), vote)
+			})
+			.collect();
+		for (voter, vote) in revealing.iter() {
+			commit::<T>(voter, hash, vote.clone());
+		}
+		close_vote::<T>(hash);
+		for (voter, vote) in revealing.iter() {
+			reveal::<T>(voter, hash, vote.clone());
+		}
+		let reveal_end = <ProposalData<T>>::get(hash).unwrap().reveal_end.unwrap();
+		frame_system::Pallet::<T>::set_block_number(reveal_end);
+		let caller: T::AccountId = account("keeper", 0, SEED);
+	}: _(SystemOrigin::Signed(caller), hash)
+	verify {
+		assert!(!<ProposalData<T>>::contains_key(&hash));
+	}
+
+	prune_archive {
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&caller, 0);
+		close_vote::<T>(hash);
+		let closed_at = <ProposalData<T>>::get(hash).unwrap().reveal_end.unwrap();
+		frame_system::Pallet::<T>::set_block_number(closed_at);
+		assert_ok!(Pallet::<T>::do_close_reveal(hash, None));
+		frame_system::Pallet::<T>::set_block_number(closed_at + T::ArchiveRetention::get());
+	}: _(SystemOrigin::Signed(caller), hash, closed_at)
+	verify {
+		assert!(!<Archive<T>>::contains_key(&hash, &closed_at));
+	}
+
+	cancel_proposal {
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&caller, 0);
+	}: _(SystemOrigin::Signed(caller), hash)
+	verify {
+		assert!(!<ProposalData<T>>::contains_key(&hash));
+	}
+
+	force_cancel {
+		let c in 0 .. T::MaxVotersPerProposal::get();
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&caller, 0);
+		for i in 0 .. c {
+			commit::<T>(&member::<T>("voter", i), hash, Vote::Yes);
+		}
+		let origin = T::CancelOrigin::successful_origin();
+	}: {
+		assert_ok!(Pallet::<T>::force_cancel(origin, hash, true));
+	}
+	verify {
+		assert!(!<ProposalData<T>>::contains_key(&hash));
+	}
+
+	force_add_member {
+		let who: T::AccountId = account("member", 0, SEED);
+		T::Currency::make_free_balance_be(&who, T::BasicDeposit::get().saturating_mul(100u32.into()));
+		let origin = T::MembershipOrigin::successful_origin();
+	}: {
+		assert_ok!(Pallet::<T>::force_add_member(origin, who.clone()));
+	}
+	verify {
+		assert!(Pallet::<T>::is_member(&who));
+	}
+
+	kick_member {
+		let p in 1 .. max_proposals::<T>();
+		fund_pot::<T>();
+		let caller = member::<T>("caller", 0);
+		let who = member::<T>("member", 0);
+		for i in 0 .. p {
+			let hash = proposal::<T>(&caller, i);
+			commit::<T>(&who, hash, Vote::Yes);
+		}
+		let origin = T::MembershipOrigin::successful_origin();
+	}: {
+		assert_ok!(Pallet::<T>::kick_member(origin, who.clone(), Perbill::from_percent(50)));
+	}
+	verify {
+		assert!(!Pallet::<T>::is_member(&who));
+	}
+
+	ban_member {
+		let p in 1 .. max_proposals::<T>();
+		fund_pot::<T>();
+		let caller = member::<T>("caller", 0);
+		let who = member::<T>("member", 0);
+		for i in 0 .. p {
+			let hash = proposal::<T>(&caller, i);
+			commit::<T>(&who, hash, Vote::Yes);
+		}
+		let until = frame_system::Pallet::<T>::block_number() + T::ArchiveRetention::get();
+		let origin = T::MembershipOrigin::successful_origin();
+	}: {
+		assert_ok!(Pallet::<T>::ban_member(origin, who.clone(), until, Perbill::from_percent(50)));
+	}
+	verify {
+		assert!(<Bans<T>>::contains_key(&who));
+	}
+
+	refresh_identity {
+		let caller = member::<T>("caller", 0);
+		let who = member::<T>("member", 0);
+		<Suspensions<T>>::insert(&who, frame_system::Pallet::<T>::block_number());
+	}: _(SystemOrigin::Signed(caller), who.clone())
+	verify {
+		assert!(!<Suspensions<T>>::contains_key(&who));
+	}
+
+	delegate {
+		let (track, _) = track::<T>();
+		let delegate = member::<T>("delegate", 0);
+		for i in 0 .. T::MaxDelegators::get() - 1 {
+			delegator::<T>(i, &delegate);
+		}
+		let caller = member::<T>("caller", 0);
+	}: _(SystemOrigin::Signed(caller.clone()), delegate.clone(), Some(track))
+	verify {
+		assert_eq!(Pallet::<T>::delegate_of(&caller, track), Some(delegate));
+	}
+
+	undelegate {
+		let delegate = member::<T>("delegate", 0);
+		for i in 0 .. T::MaxDelegators::get() - 1 {
+			delegator::<T>(i, &delegate);
+		}
+		let caller = delegator::<T>(T::MaxDelegators::get(), &delegate);
+	}: _(SystemOrigin::Signed(caller.clone()), None)
+	verify {
+		assert!(!<Delegations<T>>::contains_key(&caller, None::<TrackId>));
+	}
+
+	claim {
+		// voters on the closed proposal, the claim looks up its entries among theirs
+		let v in 1 .. T::MaxVotersPerProposal::get();
+		fund_pot::<T>();
+		let proposer = member::<T>("proposer", 0);
+		let caller = member::<T>("caller", 0);
+		let hash = proposal::<T>(&proposer, 0);
+		let voters: Vec<T::AccountId> = (1 .. v).map(|i| member::<T>("voter", i)).collect();
+		for voter in voters.iter() {
+			commit::<T>(voter, hash, Vote::Yes);
+		}
+		commit::<T>(&caller, hash, Vote::No);
+		close_vote::<T>(hash);
+		for voter in voters.iter() {
+			reveal::<T>(voter, hash, Vote::Yes);
+		}
+		reveal::<T>(&caller, hash, Vote::No);
+		let reveal_end = <ProposalData<T>>::get(hash).unwrap().reveal_end.unwrap();
+		frame_system::Pallet::<T>::set_block_number(reveal_end);
+		assert_ok!(Pallet::<T>::do_close_reveal(hash, None));
+	}: _(SystemOrigin::Signed(caller.clone()), hash, caller.clone())
+	verify {
+		assert!(!<PendingClaims<T>>::contains_key(&caller, &hash));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -6,37 +6,75 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod policy;
+pub mod rewards;
+pub mod weights;
+
+use core::cmp::Ordering;
+use frame_support::storage::with_transaction;
+use frame_support::weights::{GetDispatchInfo, Weight};
+use frame_support::traits::EnsureOrigin;
 use frame_support::traits::Get;
-use frame_support::traits::ReservableCurrency;
+use frame_support::traits::Imbalance;
+use frame_support::traits::NamedReservableCurrency;
+use frame_support::traits::OnUnbalanced;
+use frame_support::ensure;
 use frame_support::BoundedVec;
 pub use pallet::*;
+pub use weights::WeightInfo;
+use sp_std::marker::PhantomData;
 use sp_runtime::traits::AccountIdConversion;
-use sp_runtime::traits::CheckedDiv;
+use sp_runtime::traits::Dispatchable;
+use sp_runtime::traits::Hash;
+use sp_runtime::traits::One;
 use sp_runtime::traits::Saturating;
+use sp_runtime::traits::Zero;
 use sp_runtime::DispatchError;
-use sp_std::borrow::ToOwned;
+use sp_runtime::DispatchResult;
+use sp_runtime::Perbill;
+use sp_runtime::PerThing;
+use sp_runtime::TransactionOutcome;
+use sp_std::vec;
 use sp_std::vec::Vec;
+use policy::{SlashingPolicy, Voter};
+use rewards::RewardSource;
+use types::{
+	ArchivedProposal, Choices, Claim, Conviction, Leftover, OptionIndex, Outcome, Phase, Quorum,
+	Settlement, Threshold, TrackId, Transition, Vote, VoteToken, VoterBalance,
+};
 pub mod types;
 
 #[frame_support::pallet]
 pub mod pallet {
 
-	use core::cmp::Ordering;
-
-	use crate::types::{Commit, Data, Proposal, Vote, VoteToken, VoterBalance};
+	use crate::rewards::RewardSource;
+	use crate::WeightInfo;
+	use crate::types::{
+		ArchivedProposal, Commit, Conviction, Data, IdentityLevel, Leftover, MemberInfo,
+		OptionIndex, Options, Proposal, Settlement, Threshold, TrackId, TrackInfo, Transition, Vote,
+		VoteToken,
+	};
 	use frame_support::dispatch::DispatchResult;
 	use frame_support::ensure;
 	use frame_support::pallet_prelude::CountedStorageMap;
 	use frame_support::pallet_prelude::StorageDoubleMap;
 	use frame_support::pallet_prelude::StorageMap;
 	use frame_support::sp_runtime::traits::Hash;
-	use frame_support::traits::{Currency, ReservableCurrency};
+	use frame_support::traits::{
+		Currency, NamedReservableCurrency, OnUnbalanced, ReservableCurrency,
+	};
+	use frame_support::weights::{GetDispatchInfo, PostDispatchInfo};
 	use frame_support::{
 		pallet_prelude::{ValueQuery, *},
 		Identity, PalletId,
 	};
 	use frame_system::{ensure_signed, pallet_prelude::*};
-	use sp_runtime::traits::{IdentifyAccount, Member, Verify};
+	use sp_runtime::traits::Dispatchable;
+	use sp_runtime::Perbill;
 	use sp_std::boxed::Box;
 	use sp_std::vec;
 	use sp_std::vec::Vec;
@@ -48,8 +86,35 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// Shorted type for the funds taken from an account, e.g. by a slash
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Shorted type for a voter as seen by the slashing policy
+	pub type VoterOf<T> =
+		crate::policy::Voter<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// Shorted type for the data of a proposal
+	pub type ProposalOf<T> = Proposal<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	/// Shorted type for the parameters of a track
+	pub type TrackOf<T> = TrackInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
 	pub trait IdentityProvider<AccountId> {
-		fn check_existence(account: &AccountId) -> bool;
+		/// The quality of the identity of the account
+		fn judgement(account: &AccountId) -> IdentityLevel;
+		fn check_existence(account: &AccountId) -> bool {
+			Self::judgement(account) > IdentityLevel::None
+		}
+		/// Gives the account an identity meeting [`Config::MinJudgement`],
+		/// used to set up benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_identity(account: &AccountId);
 	}
 
 	#[pallet::config]
@@ -59,32 +124,96 @@ pub mod pallet {
 		/// glueing trait that provides bridge to identity pallet
 		/// In other words, allows to interact with Identity component
 		type IdentityProvider: IdentityProvider<Self::AccountId>;
-		/// Currency type, required to manipulate voters balances and deposits
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The identity quality required to join and to keep voting
+		#[pallet::constant]
+		type MinJudgement: Get<IdentityLevel>;
+		/// Currency type, required to manipulate voters balances and deposits.
+		/// The committee stake is reserved under the id of the pallet
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
 		/// The amount of funds that is required to have skin in a game
 		#[pallet::constant]
 		type BasicDeposit: Get<BalanceOf<Self>>;
-		/// The length of reveal phase
-		#[pallet::constant]
-		type RevealLength: Get<Self::BlockNumber>;
-		/// Maximum number of proposals allowed to be active in parallel.
+		/// Maximum number of proposals allowed to be active in parallel across all tracks.
 		#[pallet::constant]
 		type MaxProposals: Get<ProposalIndex>;
-		/// Minimum length of proposal
+		/// The tracks a proposal can follow, each with its own timing, deposit and thresholds
 		#[pallet::constant]
-		type MinLength: Get<Self::BlockNumber>;
+		type Tracks: Get<Vec<(TrackId, TrackOf<Self>)>>;
 		/// Minimum length of proposal
 		#[pallet::constant]
 		type MaxVotingTokens: Get<u8>;
-		// Public ket type to identify accounts and verify signatures
-		type Public: IdentifyAccount<AccountId = Self::AccountId>;
-		// Signature type to verify signed votes
-		type Signature: Verify<Signer = Self::Public> + Member + Decode + Encode + TypeInfo;
+		/// Maximum number of members delegating to the same member on a track or on every track
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+		/// Maximum number of members voting on a proposal, delegators included.
+		/// Bounds the weight of closing the proposal and of claiming on it
+		#[pallet::constant]
+		type MaxVotersPerProposal: Get<u32>;
 		/// The council's pallet id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+		/// The outer origin type, approved proposals are dispatched with [`RawOrigin::Approved`]
+		type Origin: From<RawOrigin<Self::Hash>>;
+		/// The outer call dispatch type, i.e. the runtime call a proposal can carry
+		type Proposal: Parameter
+			+ Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo>
+			+ From<frame_system::Call<Self>>
+			+ GetDispatchInfo;
+		/// Maximum weight of the call a proposal is allowed to carry
+		#[pallet::constant]
+		type MaxProposalWeight: Get<Weight>;
+		/// Weight `on_initialize` may spend on the phase transitions due at a block,
+		/// those that do not fit are deferred to the next block.
+		/// The first transition of a block is always applied
+		#[pallet::constant]
+		type MaxTransitionWeight: Get<Weight>;
+		/// How long the results of a closed proposal are kept before they can be pruned
+		#[pallet::constant]
+		type ArchiveRetention: Get<Self::BlockNumber>;
+		/// The share of the deposit slashed from a member who committed but did not reveal
+		#[pallet::constant]
+		type NonRevealPenalty: Get<Perbill>;
+		/// Whether the voting tokens of a member who did not reveal are refunded or burned
+		#[pallet::constant]
+		type RefundNonRevealed: Get<bool>;
+		/// The origin allowed to cancel any proposal, e.g. root or a technical committee
+		type CancelOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+		/// The origin allowed to add, remove and ban members
+		type MembershipOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+		/// Decides how much is slashed from the losing side and how the pot is split
+		type SlashingPolicy: crate::policy::SlashingPolicy<Self::AccountId, BalanceOf<Self>>;
+		/// Where the funds of the pot that no proposal pays out go
+		#[pallet::constant]
+		type Leftover: Get<Leftover<Self::AccountId>>;
+		/// Receives slashed stakes, forfeits and spam deposits, e.g. [`crate::rewards::Pot`],
+		/// a treasury or `()` to burn them. Only what reaches the reward source is paid out
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Pays the rewards of the winners, e.g. [`crate::rewards::Pot`] or
+		/// [`crate::rewards::Mint`]
+		type RewardSource: RewardSource<
+			Self::AccountId,
+			BalanceOf<Self>,
+			NegativeImbalanceOf<Self>,
+		>;
+		/// Paid to whoever closes a due phase, out of the deposit of the proposer
+		/// and whatever it lacks out of the carry-over of the pot
+		#[pallet::constant]
+		type KeeperBounty: Get<BalanceOf<Self>>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Origin for the voting committee
+	#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum RawOrigin<Hash> {
+		/// The proposal with the given hash has been approved by the committee
+		Approved(Hash),
 	}
 
+	/// Origin for the voting committee
+	#[pallet::origin]
+	pub type Origin<T> = RawOrigin<<T as frame_system::Config>::Hash>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -92,8 +221,24 @@ pub mod pallet {
 		Joined(T::AccountId),
 		/// Some identity left the voting committee
 		Left { account: T::AccountId, cashout: BalanceOf<T> },
-		/// A motion (given hash) has been proposed (by given account)
-		Proposed { account: T::AccountId, proposal_hash: T::Hash },
+		/// A member was removed from the committee, forfeiting a share of the stake
+		Kicked { who: T::AccountId, forfeited: BalanceOf<T> },
+		/// The account cannot join the committee until the block
+		Banned { who: T::AccountId, until: T::BlockNumber },
+		/// The identity of the member no longer meets the minimum judgement
+		Suspended(T::AccountId),
+		/// The identity of the suspended member meets the minimum judgement again
+		Reinstated(T::AccountId),
+		/// The member votes through the delegate on the track, or on every track if `None`
+		Delegated { from: T::AccountId, to: T::AccountId, track: Option<TrackId> },
+		/// The member votes on its own again on the track, or on every track if `None`
+		Undelegated { who: T::AccountId, track: Option<TrackId> },
+		/// A motion (given hash) has been proposed (by given account) reserving the deposit
+		Proposed { account: T::AccountId, proposal_hash: T::Hash, deposit: BalanceOf<T> },
+		/// The deposit of a closed proposal has been returned to the proposer
+		DepositRefunded { proposal_hash: T::Hash, who: T::AccountId, amount: BalanceOf<T> },
+		/// The deposit of a proposal nobody revealed a vote on has been slashed
+		DepositSlashed { proposal_hash: T::Hash, who: T::AccountId, amount: BalanceOf<T> },
 		/// A motion (given hash) has been voted on by given account, leaving
 		/// a tally (yes votes and no votes given respectively as `MemberCount`).
 		Voted { account: T::AccountId, proposal_hash: T::Hash },
@@ -102,19 +247,56 @@ pub mod pallet {
 		Committed { account: T::AccountId, proposal_hash: T::Hash },
 		/// The reveal phase was finished
 		ClosedCommit(T::Hash),
+		/// Every eligible member has committed, the commit phase can be closed early
+		CommitComplete(T::Hash),
+		/// Every commit has been revealed, the reveal phase can be closed early
+		RevealComplete(T::Hash),
+		/// The account closed a due phase of the proposal and was paid the bounty
+		KeeperPaid { who: T::AccountId, proposal_hash: T::Hash, bounty: BalanceOf<T> },
 		/// Proposal has been approved
 		Approved(T::Hash),
 		/// Proposal has nit been approved
 		Disapproved(T::Hash),
+		/// The majority for the proposal fell short of its threshold, nobody is slashed
+		Rejected(T::Hash),
 		/// No consensus has been reached in motion
 		Tie(T::Hash),
+		/// Too few votes were revealed to decide on the motion
+		NoQuorum(T::Hash),
+		/// The motion has been cancelled before it was decided
+		Cancelled(T::Hash),
+		/// The option of a multi-option proposal with the most votes has been chosen
+		Chosen { proposal_hash: T::Hash, option: OptionIndex },
+		/// The scheduled phase transition failed, the proposal can still be closed manually
+		TransitionFailed { transition: Transition<T::Hash>, error: DispatchError },
+		/// The call of an approved proposal has been dispatched
+		Executed { proposal_hash: T::Hash, result: DispatchResult },
+		/// A member committed and did not reveal by the end of reveal phase
+		NonRevealed { who: T::AccountId, slashed: BalanceOf<T> },
+		/// The votes of the member on a closed proposal have been settled
+		Claimed {
+			who: T::AccountId,
+			proposal_hash: T::Hash,
+			slashed: BalanceOf<T>,
+			reward: BalanceOf<T>,
+		},
+		/// Every voter of a closed proposal has claimed, `payout` was slashed or carried over
+		Settled { proposal_hash: T::Hash, payout: BalanceOf<T> },
+		/// Funds of the pot no proposal pays out were kept for the next proposal with winners
+		LeftoverCarried { amount: BalanceOf<T> },
+		/// Funds of the pot no proposal pays out were moved to the account
+		LeftoverTransferred { to: T::AccountId, amount: BalanceOf<T> },
+		/// Funds of the pot no proposal pays out were burned
+		LeftoverBurned { amount: BalanceOf<T> },
+		/// The archived results of a proposal were pruned
+		Pruned { proposal_hash: T::Hash, closed_at: T::BlockNumber },
 		/// The voting phase was closed
 		ClosedReveal {
 			proposal_hash: T::Hash,
 			yes: MemberCount,
 			no: MemberCount,
+			abstain: MemberCount,
 			revealed: MemberCount,
-			payout: BalanceOf<T>,
 		},
 	}
 
@@ -156,15 +338,61 @@ pub mod pallet {
 		RevealEnded,
 		/// No commit has been submitted
 		NoCommit,
-		/// Could not verify signature of a commit
-		SignatureInvalid,
+		/// The revealed vote does not match the commitment
+		CommitmentMismatch,
 		/// The voter is in the middle of vote
 		InMotion,
+		/// The call of a proposal exceeds `MaxProposalWeight`
+		ProposalTooHeavy,
+		/// No archived results of the proposal closed at the given block
+		NotArchived,
+		/// The vote does not fit the options of the proposal
+		InvalidOptions,
+		/// The threshold is not allowed by the track
+		ThresholdNotAllowed,
+		/// The track does not exist
+		BadTrack,
+		/// The track has too many active proposals
+		TrackFull,
+		/// Only the proposer can withdraw the proposal
+		NotProposer,
+		/// The proposal cannot be withdrawn once votes have been committed
+		VotesCommitted,
+		/// The account is banned from joining the committee
+		Banned,
+		/// The identity has not been judged good enough
+		InsufficientJudgement,
+		/// The identity of the member no longer meets the minimum judgement
+		Suspended,
+		/// The delegate must be another member
+		InvalidDelegate,
+		/// Delegates cannot delegate and delegators cannot be delegated to
+		DelegationChain,
+		/// The delegate already represents `MaxDelegators` members
+		TooManyDelegators,
+		/// The member does not delegate on the track
+		NotDelegating,
+		/// The member votes through a delegate on the track of the proposal
+		Delegated,
+		/// The stake is locked by a conviction vote
+		ConvictionLocked,
+		/// The proposal has `MaxVotersPerProposal` voters
+		TooManyVoters,
+		/// The account has nothing to claim on the proposal
+		NothingToClaim,
+		/// The winners are paid once the losing side and the non-revealers have been slashed
+		SlashesPending,
+		/// The proposal was closed before and its voters have not claimed everything yet
+		SettlementPending,
 	}
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	//we use unbounded storage because we size of council can vary
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 	/// Collection of all proposals hashes
@@ -174,18 +402,158 @@ pub mod pallet {
 	/// The actual data of proposal
 	#[pallet::storage]
 	pub type ProposalData<T: Config> =
-		StorageMap<_, Identity, T::Hash, Proposal<T::AccountId, T::BlockNumber, BalanceOf<T>>>;
+		StorageMap<_, Identity, T::Hash, ProposalOf<T>>;
+	/// Runtime calls carried by callable proposals, dispatched once the proposal is approved
+	#[pallet::storage]
+	pub type ProposalCalls<T: Config> =
+		StorageMap<_, Identity, T::Hash, <T as Config>::Proposal>;
+	/// Results of closed proposals by the block they were closed at.
+	/// The same proposal can be submitted again once it has been closed and settled
+	#[pallet::storage]
+	pub type Archive<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::Hash,
+		Twox64Concat,
+		T::BlockNumber,
+		ArchivedProposal<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	>;
+	/// The number of active proposals of each track
+	#[pallet::storage]
+	pub type TrackProposals<T: Config> = StorageMap<_, Twox64Concat, TrackId, u32, ValueQuery>;
+	/// Phase transitions of proposals due at a block, applied in `on_initialize`
+	#[pallet::storage]
+	pub type Schedule<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Transition<T::Hash>, T::MaxProposals>,
+		ValueQuery,
+	>;
 	/// The list of council member with their voting tokens
 	#[pallet::storage]
 	pub type Members<T: Config> =
-		CountedStorageMap<_, Identity, T::AccountId, VoterBalance<BalanceOf<T>>, ValueQuery>;
+		CountedStorageMap<_, Identity, T::AccountId, MemberInfo<BalanceOf<T>>, ValueQuery>;
+	/// Accounts banned from joining the committee until the block
+	#[pallet::storage]
+	pub type Bans<T: Config> = StorageMap<_, Identity, T::AccountId, T::BlockNumber>;
+	/// Members whose identity was found below the minimum judgement, with the block it was found at
+	#[pallet::storage]
+	pub type Suspensions<T: Config> =
+		CountedStorageMap<_, Identity, T::AccountId, T::BlockNumber>;
+	/// Members added by the membership origin, who need no identity
+	#[pallet::storage]
+	pub type ForceAdded<T: Config> = StorageMap<_, Identity, T::AccountId, ()>;
 	/// Vote commits submitted by voters
 	#[pallet::storage]
 	pub type Commits<T: Config> =
-		StorageDoubleMap<_, Identity, T::AccountId, Identity, T::Hash, Commit<T::Signature>>;
+		StorageDoubleMap<_, Identity, T::AccountId, Identity, T::Hash, Commit<T::Hash>>;
+	/// The block until which the stake of a member is locked by conviction votes
+	#[pallet::storage]
+	pub type ConvictionLocks<T: Config> = StorageMap<_, Identity, T::AccountId, T::BlockNumber>;
+	/// The delegate of a member on a track, or on every track for `None`
+	#[pallet::storage]
+	pub type Delegations<T: Config> =
+		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, Option<TrackId>, T::AccountId>;
+	/// The members delegating to a member on a track, or on every track for `None`
+	#[pallet::storage]
+	pub type Delegators<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		Option<TrackId>,
+		BoundedVec<T::AccountId, T::MaxDelegators>,
+		ValueQuery,
+	>;
+	/// What the voters of closed proposals have left to claim
+	#[pallet::storage]
+	pub type Settlements<T: Config> = StorageMap<
+		_,
+		Identity,
+		T::Hash,
+		Settlement<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	>;
+	/// The funds of the pot held for each closed proposal until every voter has claimed
+	#[pallet::storage]
+	pub type PotLedger<T: Config> = StorageMap<_, Identity, T::Hash, BalanceOf<T>, ValueQuery>;
+	/// The funds of the pot carried over to the reward of the next proposal with winners
+	#[pallet::storage]
+	pub type CarryOver<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+	/// The proposals a member has voted on, directly or through a delegate, and not claimed yet
+	#[pallet::storage]
+	pub type PendingClaims<T: Config> =
+		StorageDoubleMap<_, Identity, T::AccountId, Identity, T::Hash, ()>;
+	/// The votes the commit of a delegate carries for its delegators, until it is revealed
+	#[pallet::storage]
+	pub type DelegatedVotes<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::Hash,
+		Identity,
+		T::AccountId,
+		Vec<(T::AccountId, VoteToken)>,
+		ValueQuery,
+	>;
+	/// The number of times accounts joined the committee, the last join index handed out
+	#[pallet::storage]
+	pub type JoinCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+	/// The order in which the member joined the committee, counting from 1.
+	/// Members who joined before the order was recorded have 0
+	#[pallet::storage]
+	pub type JoinIndex<T: Config> = StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+	/// The eligible members counted in the turnout of a proposal in its commit phase
+	#[pallet::storage]
+	pub type Turnout<T: Config> =
+		StorageDoubleMap<_, Identity, T::Hash, Identity, T::AccountId, ()>;
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Moves the proposals which are due at this block to their next phase,
+		/// as many as fit in `MaxTransitionWeight`
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let mut applied = false;
+			let mut deferred = Vec::new();
+			for transition in <Schedule<T>>::take(now) {
+				let worst = Self::transition_weight(&transition);
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				//the transitions keep their order, the first one always goes through
+				let fits = weight.saturating_add(worst) <= T::MaxTransitionWeight::get();
+				if applied && (!fits || !deferred.is_empty()) {
+					deferred.push(transition);
+					continue
+				}
+				applied = true;
+				// a failed transition leaves the proposal in its phase,
+				// it can still be closed manually
+				let consumed = match Self::apply_transition(transition.clone()) {
+					Ok(consumed) => consumed,
+					Err(error) => {
+						Self::deposit_event(Event::<T>::TransitionFailed { transition, error });
+						worst
+					},
+				};
+				weight = weight.saturating_add(consumed);
+			}
+			if !deferred.is_empty() {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				Self::defer(now, deferred);
+			}
+			weight
+		}
+
+		/// Checks the pot against its ledger once the migrations ran
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::check_pot()
+		}
+
+		fn integrity_test() {
+			//deposits must never be slashed as stake and vice versa
+			assert_ne!(Self::reserve_id(), Self::deposit_reserve_id());
+		}
+	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig;
@@ -200,19 +568,14 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
-			// Create pot account
-			let account_id = <Pallet<T>>::account_id();
-			let min = T::Currency::minimum_balance();
-			if T::Currency::free_balance(&account_id) < min {
-				let _ = T::Currency::make_free_balance_be(&account_id, min);
-			}
+			T::RewardSource::endow();
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Join committee and deposit money to have skin in a game
-		#[pallet::weight(10_000_000)]
+		#[pallet::weight(T::WeightInfo::join_committee())]
 		pub fn join_committee(origin: OriginFor<T>) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
@@ -221,245 +584,212 @@ pub mod pallet {
 
 			//check if signer has identity | tested
 			ensure!(T::IdentityProvider::check_existence(&signer), Error::<T>::NoIdentity);
+			ensure!(Self::is_judged(&signer), Error::<T>::InsufficientJudgement);
 
-			//check if the account has enough money to deposit
-			ensure!(
-				T::Currency::can_reserve(&signer, T::BasicDeposit::get()),
-				Error::<T>::NotEnoughFunds
-			);
-
-			T::Currency::reserve(&signer, T::BasicDeposit::get())?;
-
-			//deposit 100 voting tokens to the voter
-			Self::deposit_votes(&signer, T::MaxVotingTokens::get());
-
-			//reserve the fixed amount specified in the config
-			Self::set_reserved_balance(&signer, T::BasicDeposit::get());
-
-			Self::deposit_event(Event::<T>::Joined(signer));
+			//the ban is lifted once it expires
+			if let Some(until) = <Bans<T>>::get(&signer) {
+				let current_block = frame_system::Pallet::<T>::block_number();
+				ensure!(until <= current_block, Error::<T>::Banned);
+				<Bans<T>>::remove(&signer);
+			}
 
-			Ok(())
+			Self::do_join(signer)
 		}
 
-		/// leave committee and cash out
-		#[pallet::weight(10_000_000)]
-		pub fn leave_committee(origin: OriginFor<T>) -> DispatchResult {
+		/// leave committee and cash out.
+		/// The votes on closed proposals are claimed first
+		#[pallet::weight(
+			T::WeightInfo::leave_committee()
+				.saturating_add(Pallet::<T>::claims_weight(T::MaxProposals::get()))
+		)]
+		pub fn leave_committee(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let signer = ensure_signed(origin)?;
 
-			//check if signer has identity | tested
-			ensure!(T::IdentityProvider::check_existence(&signer), Error::<T>::NoIdentity);
+			//members whose identity was cleared can still cash out
+			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+			let claimed = Self::claim_closed(&signer)?;
 
 			// ensure that user is not in the middle of voting process
 			let active_votes = <Commits<T>>::iter_prefix_values(signer.clone()).count();
 			ensure!(active_votes == 0, Error::<T>::InMotion);
+			//nor waiting for the votes or the rewards of proposals to be settled
+			let unclaimed = <PendingClaims<T>>::iter_key_prefix(&signer).next().is_some();
+			ensure!(!unclaimed, Error::<T>::InMotion);
+			//nor while the stake backs a conviction vote
+			if let Some(until) = <ConvictionLocks<T>>::get(&signer) {
+				let current_block = frame_system::Pallet::<T>::block_number();
+				ensure!(until <= current_block, Error::<T>::ConvictionLocked);
+				<ConvictionLocks<T>>::remove(&signer);
+			}
 
 			// find the exact amount of reserved funds that need to be returned to the free balance
 			let reserved_balance = <Members<T>>::get(signer.clone()).reserved_balance;
-			let balance = T::Currency::unreserve(&signer, reserved_balance);
+			let leftover = T::Currency::unreserve_named(&Self::reserve_id(), &signer, reserved_balance);
+			let balance = reserved_balance.saturating_sub(leftover);
 			//remove entries
+			if !<Suspensions<T>>::contains_key(&signer) {
+				Self::adjust_electorate(&signer, false);
+			}
 			<Members<T>>::remove(signer.clone());
+			<Suspensions<T>>::remove(&signer);
+			<ForceAdded<T>>::remove(&signer);
+			<JoinIndex<T>>::remove(&signer);
+			Self::clear_delegations(&signer);
 
 			Self::deposit_event(Event::<T>::Left { account: signer, cashout: balance });
 
-			Ok(())
+			let claims = Self::claims_weight(claimed);
+			Ok(Some(T::WeightInfo::leave_committee().saturating_add(claims)).into())
 		}
 
 		/// Creates the proposal with given text and duration in blocks
-		#[pallet::weight(10_000_000)]
+		#[pallet::weight(T::WeightInfo::create_proposal(T::MaxProposals::get()))]
 		pub fn create_proposal(
 			origin: OriginFor<T>,
 			proposal_text: Box<Data>,
+			track: TrackId,
+			threshold: Threshold,
 			duration: T::BlockNumber,
 		) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
-			if duration < T::MinLength::get() {
-				ensure!(false, Error::<T>::WrongProposalLength);
-			}
-
-			//check if signer is a member already | tested
-			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
-
-			// ensure that we don't have too many proposal
-			let length_res = <Proposals<T>>::decode_len();
-			if let Some(length) = length_res {
-				if length == T::MaxProposals::get() as usize {
-					ensure!(false, Error::<T>::TooManyProposals);
-				}
-			}
-
-			// ensure that proposal exists
 			let proposal_hash = T::Hashing::hash_of(&proposal_text);
-			let (exist, _) = Self::proposal_exist(&proposal_hash);
-			ensure!(!exist, Error::<T>::DuplicateProposal);
-
-			// try to append, if error happens, this is probably we have too many proposals
-			ensure!(
-				<Proposals<T>>::try_append(proposal_hash).is_ok(),
-				Error::<T>::TooManyProposals
-			);
-
-			// calculate the end block of proposal
-			let end = duration + frame_system::Pallet::<T>::block_number();
-
-			// construct the proposal object
-			let proposal = Proposal {
-				title: *proposal_text,
-				proposer: signer.clone(),
-				ayes: 0,
-				nays: 0,
-				poll_end: end,
-				reveal_end: None,
-				votes: Vec::new(),
-				revealed: Vec::new(),
-				payout: BalanceOf::<T>::default(),
-				closed: false
-			};
-
-			<ProposalData<T>>::insert(proposal_hash, proposal);
-			Self::deposit_event(Event::<T>::Proposed { account: signer, proposal_hash });
-
-			Ok(())
+			Self::do_create_proposal(
+				signer,
+				proposal_hash,
+				*proposal_text,
+				Default::default(),
+				track,
+				threshold,
+				duration,
+			)
 		}
 
-		/// Closes the vote and starts revealing phase
-		#[pallet::weight(10_000_000)]
-		pub fn close_vote(origin: OriginFor<T>, proposal: T::Hash) -> DispatchResult {
+		/// Creates the proposal which asks to pick one of the given options
+		#[pallet::weight(T::WeightInfo::create_multi_option_proposal(T::MaxProposals::get()))]
+		pub fn create_multi_option_proposal(
+			origin: OriginFor<T>,
+			proposal_text: Box<Data>,
+			options: Options,
+			track: TrackId,
+			threshold: Threshold,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
-			//check if signer is a member already | tested
-			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
-
-			//ensure that proposal data exists
-			let proposal_data = <ProposalData<T>>::get(&proposal);
-			ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
-
-			//if we are here, then we know that data exists and we can unwrap it
-			let mut proposal_data = proposal_data.unwrap();
-
-			// if reveal end is set, then we know that voting phase ended
-			ensure!(proposal_data.reveal_end.is_none(), Error::<T>::VoteAlreadyEnded);
-
-			//make sure that we don't close voting phase too early
-			let current_block = frame_system::Pallet::<T>::block_number();
-			ensure!(proposal_data.poll_end <= current_block, Error::<T>::TooEarly);
+			ensure!(options.len() >= 2, Error::<T>::InvalidOptions);
+
+			let proposal_hash = T::Hashing::hash_of(&(&proposal_text, &options));
+			Self::do_create_proposal(
+				signer,
+				proposal_hash,
+				*proposal_text,
+				options,
+				track,
+				threshold,
+				duration,
+			)
+		}
 
-			// set the end of reveal phase
-			let current_block = frame_system::Pallet::<T>::block_number();
-			proposal_data.reveal_end = Some(current_block + T::RevealLength::get());
+		/// Creates the proposal which dispatches the given call once approved
+		#[pallet::weight(T::WeightInfo::create_call_proposal(T::MaxProposals::get()))]
+		pub fn create_call_proposal(
+			origin: OriginFor<T>,
+			proposal_text: Box<Data>,
+			call: Box<<T as Config>::Proposal>,
+			track: TrackId,
+			threshold: Threshold,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
 
-			<ProposalData<T>>::insert(proposal, proposal_data);
+			// the call is dispatched in `close_reveal`, so its weight must be bounded
+			let call_weight = call.get_dispatch_info().weight;
+			ensure!(call_weight <= T::MaxProposalWeight::get(), Error::<T>::ProposalTooHeavy);
+
+			let proposal_hash = T::Hashing::hash_of(&(&proposal_text, &call));
+			Self::do_create_proposal(
+				signer,
+				proposal_hash,
+				*proposal_text,
+				Default::default(),
+				track,
+				threshold,
+				duration,
+			)?;
+			<ProposalCalls<T>>::insert(proposal_hash, *call);
 
-			Self::deposit_event(Event::<T>::ClosedCommit(proposal));
 			Ok(())
 		}
 
-		/// Closes the reveal and announces the results
-		#[pallet::weight(10_000_000)]
-		pub fn close_reveal(origin: OriginFor<T>, proposal: T::Hash) -> DispatchResult {
+		/// Closes the vote and starts revealing phase.
+		/// The phase is closed automatically at `poll_end`, the call allows to trigger it
+		/// if that failed or as soon as every eligible member has committed.
+		/// Anyone can call it, closing a due phase is free and pays `KeeperBounty`,
+		/// a premature call is rejected and pays the fee
+		#[pallet::weight(T::WeightInfo::close_vote(T::MaxVotersPerProposal::get()))]
+		pub fn close_vote(origin: OriginFor<T>, proposal: T::Hash) -> DispatchResultWithPostInfo {
 			let signer = ensure_signed(origin)?;
 
-			//check if signer is a member already | tested
-			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+			let weight = Self::do_close_vote(proposal, Some(&signer))?;
+			Ok(PostDispatchInfo { actual_weight: Some(weight), pays_fee: Pays::No })
+		}
 
-			//ensure that proposal data exists
-			let proposal_data = <ProposalData<T>>::get(&proposal);
-			ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
+		/// Closes the reveal and announces the results.
+		/// Dispatches the call of the proposal if it has been approved.
+		/// The phase is closed automatically at `reveal_end`, the call allows to trigger it
+		/// if that failed or as soon as every commit has been revealed.
+		/// Anyone can call it, closing a due phase is free and pays `KeeperBounty`,
+		/// a premature call is rejected and pays the fee
+		#[pallet::weight(
+			T::WeightInfo::close_reveal(T::MaxVotersPerProposal::get())
+				.saturating_add(T::MaxProposalWeight::get())
+		)]
+		pub fn close_reveal(origin: OriginFor<T>, proposal: T::Hash) -> DispatchResultWithPostInfo {
+			let signer = ensure_signed(origin)?;
 
-			//if we are here, then we know that data exists and we can unwrap it
-			let mut proposal_data = proposal_data.unwrap();
+			let weight = Self::do_close_reveal(proposal, Some(&signer))?;
+			Ok(PostDispatchInfo { actual_weight: Some(weight), pays_fee: Pays::No })
+		}
+
+		/// Prunes the archived results of a proposal after `ArchiveRetention` blocks
+		#[pallet::weight(T::WeightInfo::prune_archive())]
+		pub fn prune_archive(
+			origin: OriginFor<T>,
+			proposal: T::Hash,
+			closed_at: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
 
-			//if reveal phase end is not set, that means that we did not start it
-			ensure!(proposal_data.reveal_end.is_some(), Error::<T>::RevealNotStarted);
-			//if reveal phase end is not set, that means that we did not start it
-			ensure!(!proposal_data.closed, Error::<T>::RevealEnded);
+			ensure!(<Archive<T>>::contains_key(proposal, closed_at), Error::<T>::NotArchived);
 
-			let reveal_end = proposal_data.reveal_end.unwrap();
 			let current_block = frame_system::Pallet::<T>::block_number();
-			ensure!(reveal_end <= current_block, Error::<T>::TooEarly);
-
-			//refund voting tokens to voters
-			for (_, (account, votes, _)) in proposal_data.votes.iter().enumerate() {
-				let amount = u8::pow(*votes, 2);
-				Self::deposit_votes(account, amount);
-			}
-
-			//deduce winning side, slash and reward voters
-			let result = proposal_data.ayes.cmp(&proposal_data.nays);
-			let pot_address = Self::account_id();
-			let amount: BalanceOf<T>;
-			match result {
-				Ordering::Greater => {
-					let losers: Vec<T::AccountId> = proposal_data
-						.votes
-						.iter()
-						.filter(|entry| entry.2 == Vote::No)
-						.map(|entry| entry.0.clone())
-						.collect();
-					amount = Self::slash_voting_side(losers, &pot_address)?;
-					let winners: Vec<T::AccountId> = proposal_data
-						.votes
-						.iter()
-						.filter(|entry| entry.2 == Vote::Yes)
-						.map(|entry| entry.0.clone())
-						.collect();
-					Self::reward_voting_side(winners, &pot_address, amount)?;
-					Self::deposit_event(Event::<T>::Approved(proposal));
-				},
-				Ordering::Less => {
-					let losers: Vec<T::AccountId> = proposal_data
-						.votes
-						.iter()
-						.filter(|entry| entry.2 == Vote::Yes)
-						.map(|entry| entry.0.clone())
-						.collect();
-					amount = Self::slash_voting_side(losers, &pot_address)?;
-					let winners: Vec<T::AccountId> = proposal_data
-						.votes
-						.iter()
-						.filter(|entry| entry.2 == Vote::No)
-						.map(|entry| entry.0.clone())
-						.collect();
-					Self::reward_voting_side(winners, &pot_address, amount)?;
-					Self::deposit_event(Event::<T>::Disapproved(proposal));
-				},
-				Ordering::Equal => {
-					let losers: Vec<T::AccountId> =
-						proposal_data.votes.iter().map(|entry| entry.0.clone()).collect();
-					amount = Self::slash_voting_side(losers, &pot_address)?;
-					Self::reward_voting_side(
-						vec![proposal_data.clone().proposer],
-						&pot_address,
-						amount,
-					)?;
-					Self::deposit_event(Event::<T>::Tie(proposal));
-				},
-			}
-
-			//set the amount that was slashed and paid
-			proposal_data.payout = amount;
-			//close proposal
-			proposal_data.closed = true;
-			<ProposalData<T>>::insert(&proposal, proposal_data.clone());
-
-			Self::deposit_event(Event::<T>::ClosedReveal {
-				proposal_hash: proposal,
-				yes: proposal_data.ayes,
-				no: proposal_data.nays,
-				revealed: proposal_data.revealed.len() as u32,
-				payout: proposal_data.payout,
-			});
+			let prunable_at = closed_at.saturating_add(T::ArchiveRetention::get());
+			ensure!(prunable_at <= current_block, Error::<T>::TooEarly);
+
+			<Archive<T>>::remove(proposal, closed_at);
+
+			Self::deposit_event(Event::<T>::Pruned { proposal_hash: proposal, closed_at });
 			Ok(())
 		}
 
-		/// Reveal your vote.
+		/// Reveal your vote together with the secret salt of the commitment.
 		/// Can be done anytime before reveal vote timeout but is not incentivised
-		#[pallet::weight(10_000_000)]
-		pub fn reveal_vote(origin: OriginFor<T>, proposal: T::Hash, vote: Vote) -> DispatchResult {
+		#[pallet::weight(
+			T::WeightInfo::reveal_vote(T::MaxVotersPerProposal::get(), T::MaxDelegators::get())
+		)]
+		pub fn reveal_vote(
+			origin: OriginFor<T>,
+			proposal: T::Hash,
+			vote: Vote,
+			salt: [u8; 32],
+		) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
 			//check if signer is a member already | tested
 			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+			//the commits left on a closed proposal are settled by claiming
+			ensure!(!<Settlements<T>>::contains_key(&proposal), Error::<T>::RevealEnded);
 
 			//verify the signature exists
 			let commit = <Commits<T>>::take(&signer, &proposal);
@@ -467,66 +797,95 @@ pub mod pallet {
 			let commit = commit.unwrap();
 
 			let proposal_data = <ProposalData<T>>::get(&proposal);
-			ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
-			let mut proposal_data = proposal_data.unwrap();
-
-			let reveal_exist = proposal_data.reveal_end;
+			let current_block = frame_system::Pallet::<T>::block_number();
 
-			if let Some(reveal_end) = reveal_exist {
-				let current_block = frame_system::Pallet::<T>::block_number();
+			// the reveal phase is over if the proposal has already been archived
+			let late = match &proposal_data {
+				Some(data) => data.reveal_end.map_or(false, |reveal_end| current_block > reveal_end),
+				None => true,
+			};
 
-				// if voter decides to reveal votes after the end, he will just be slashed
-				// the voter is incentivised to perform this action in order to refund voting tokens
-				// or to cash out
-				if current_block > reveal_end {
-					let pot_address = Self::account_id();
-					let _ = Self::slash_voting_side(vec![signer.clone()], &pot_address)?;
-					let amount = u8::pow(commit.number, 2);
-					Self::deposit_votes(&signer, amount);
-
-					//probably need to refund, but let it be additional punishment
-					return Ok(());
-				}
+			// if voter decides to reveal votes after the end, he will just be slashed
+			// the voter is incentivised to perform this action in order to refund voting tokens
+			// or to cash out
+			if late {
+				let (_, credited) =
+					Self::slash_voting_side(vec![(signer.clone(), commit.number)], 0)?;
+				//no proposal pays out the slash anymore
+				Self::release_leftover(credited)?;
+				let amount = proposal_data
+					.as_ref()
+					.map_or(commit.number.saturating_pow(2), |data| data.cost(commit.number));
+				Self::deposit_votes(&signer, amount);
+				//the delegators are not punished for the delegate
+				let multi_option = proposal_data.as_ref().map_or(false, Proposal::is_multi_option);
+				Self::refund_delegated_votes(&proposal, &signer, multi_option);
+
+				//probably need to refund, but let it be additional punishment
+				return Ok(());
 			}
 
-			//get the data that supposed to be signed
-			let data = (vote.clone(), commit.salt).encode();
-			//and check signature validity
-			let valid_sign = commit.signature.verify(data.as_slice(), &signer);
-			ensure!(valid_sign, Error::<T>::SignatureInvalid);
+			let mut proposal_data = proposal_data.ok_or(Error::<T>::ProposalMissing)?;
+
+			//recompute the commitment from the revealed preimage
+			let commitment = Self::commitment_of(&signer, &proposal, &vote, commit.number, &salt);
+			ensure!(commitment == commit.commitment, Error::<T>::CommitmentMismatch);
 
 			let voted = Self::already_voted(&signer, &proposal_data);
 			ensure!(!voted, Error::<T>::DuplicateVote);
 
-			match vote {
-				Vote::Yes => proposal_data.ayes += commit.number as u32,
-				Vote::No => proposal_data.nays += commit.number as u32,
+			match (&vote, proposal_data.is_multi_option()) {
+				(Vote::Yes, false) | (Vote::No, false) | (Vote::Abstain, _) => {},
+				(Vote::Options(choices), true) => ensure!(
+					Self::valid_choices(choices, commit.number, proposal_data.options.len()),
+					Error::<T>::InvalidOptions
+				),
+				_ => return Err(Error::<T>::InvalidOptions.into()),
 			}
 
-			//push the vote counters
-			proposal_data.votes.push((signer.clone(), commit.number, vote));
-			//update the list of voters that revealed their choices
-			proposal_data.revealed.push(signer.clone());
+			proposal_data.count(signer.clone(), commit.number, vote.clone(), commit.conviction);
+			proposal_data.unrevealed = proposal_data.unrevealed.saturating_sub(1);
+			//votes may be revealed before the commit phase is over
+			let complete = proposal_data.reveal_end.is_some() && proposal_data.all_revealed();
+			//the delegators vote the same way with the number they could afford,
+			//the conviction of the delegate never locks their stake
+			for (delegator, number) in <DelegatedVotes<T>>::take(&proposal, &signer) {
+				//those who left the committee lost their voting tokens
+				if Self::is_member(&delegator) {
+					<PendingClaims<T>>::insert(&delegator, proposal, ());
+					proposal_data.count(delegator, number, vote.clone(), Conviction::None);
+				}
+			}
 
 			<ProposalData<T>>::insert(proposal, proposal_data);
 
 			Self::deposit_event(Event::<T>::Voted { account: signer, proposal_hash: proposal });
+			if complete {
+				Self::deposit_event(Event::<T>::RevealComplete(proposal));
+			}
 
 			Ok(())
 		}
 
-		/// Secretly submit the vote with the salt
-		#[pallet::weight(10_000_000)]
+		/// Secretly submit the vote as a hash commitment.
+		/// See [`Pallet::commitment_of`] for the preimage of the commitment.
+		/// The votes on closed proposals are claimed first to refund the voting tokens
+		#[pallet::weight(
+			T::WeightInfo::commit_vote(T::MaxVotersPerProposal::get(), T::MaxDelegators::get())
+				.saturating_add(Pallet::<T>::claims_weight(T::MaxProposals::get()))
+		)]
 		pub fn commit_vote(
 			origin: OriginFor<T>,
 			proposal: T::Hash,
-			data: T::Signature,
+			commitment: T::Hash,
 			number: VoteToken,
-			salt: u32,
-		) -> DispatchResult {
+			conviction: Conviction,
+		) -> DispatchResultWithPostInfo {
 			let signer = ensure_signed(origin)?;
 			//check if signer is a member already | tested
 			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+			ensure!(Self::in_good_standing(&signer), Error::<T>::Suspended);
+			let claimed = Self::claim_closed(&signer)?;
 
 			if number == 0 {
 				ensure!(false, Error::<T>::InvalidArgument);
@@ -539,30 +898,981 @@ pub mod pallet {
 			//ensure that proposal data exists
 			let proposal_data = <ProposalData<T>>::get(&proposal);
 			ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
-			let proposal_data = proposal_data.unwrap();
+			let mut proposal_data = proposal_data.unwrap();
 
 			//ensure that we don't commit to finished proposal
 			let current_block = frame_system::Pallet::<T>::block_number();
 			ensure!(current_block < proposal_data.poll_end, Error::<T>::VoteEnded);
 
+			//delegators vote through their delegate
+			ensure!(
+				Self::delegate_of(&signer, proposal_data.track).is_none(),
+				Error::<T>::Delegated
+			);
+			ensure!(
+				proposal_data.voters < T::MaxVotersPerProposal::get(),
+				Error::<T>::TooManyVoters
+			);
+
 			//subtract voting tokens based on quadratic scale
-			//i.e. tokens=vote^2
+			//i.e. tokens=vote^2, or the committed sum of squares for multi-option proposals
 			//make sure that voter has enough voting tokens
-			let enough_tokens = Self::decrease_votes(&signer, number.pow(2));
+			let enough_tokens = Self::decrease_votes(&signer, proposal_data.cost(number));
 			ensure!(enough_tokens, Error::<T>::NotEnoughVotingTokens);
 
 			//create commit instance
-			let commit = Commit { signature: data, salt, number };
+			let commit = Commit { commitment, number, conviction };
 			<Commits<T>>::insert(signer.clone(), proposal, commit);
+			<PendingClaims<T>>::insert(&signer, proposal, ());
+			let waiting = !proposal_data.all_committed();
+			proposal_data.committed.push(signer.clone());
+			proposal_data.voters += 1;
+			proposal_data.unrevealed += 1;
+			Self::count_turnout(&signer, &proposal, &mut proposal_data);
+			Self::commit_delegated_votes(&signer, &proposal, &mut proposal_data, number);
+			let complete = waiting && proposal_data.all_committed();
+			let voters = proposal_data.committed.len() as u32;
+			<ProposalData<T>>::insert(proposal, proposal_data);
 
 			Self::deposit_event(Event::<T>::Committed { account: signer, proposal_hash: proposal });
+			if complete {
+				Self::deposit_event(Event::<T>::CommitComplete(proposal));
+			}
+
+			let delegators = T::MaxDelegators::get();
+			let weight = T::WeightInfo::commit_vote(voters, delegators)
+				.saturating_add(Self::claims_weight(claimed));
+			Ok(Some(weight).into())
+		}
+
+		/// Adds the account to the committee without checking its identity or bans,
+		/// e.g. to bootstrap the committee. The stake is reserved as usual
+		/// and the member is never suspended for its identity
+		#[pallet::weight(T::WeightInfo::force_add_member())]
+		pub fn force_add_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+
+			ensure!(!Self::is_member(&who), Error::<T>::AlreadyMember);
+			Self::do_join(who.clone())?;
+			<ForceAdded<T>>::insert(&who, ());
+			Ok(())
+		}
+
+		/// Removes the member from the committee and slashes the given share of the stake.
+		/// Pending votes of the member are withdrawn
+		#[pallet::weight(T::WeightInfo::kick_member(T::MaxProposals::get()))]
+		pub fn kick_member(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			forfeit: Perbill,
+		) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::is_member(&who), Error::<T>::NotMember);
+			Self::do_kick(&who, forfeit)
+		}
+
+		/// Bans the account from joining the committee until the given block.
+		/// A member is removed first, forfeiting the given share of the stake
+		#[pallet::weight(T::WeightInfo::ban_member(T::MaxProposals::get()))]
+		pub fn ban_member(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			until: T::BlockNumber,
+			forfeit: Perbill,
+		) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+
+			if Self::is_member(&who) {
+				Self::do_kick(&who, forfeit)?;
+			}
+			<Bans<T>>::insert(&who, until);
+
+			Self::deposit_event(Event::<T>::Banned { who, until });
+			Ok(())
+		}
+
+		/// Checks the identity of the member against the minimum judgement,
+		/// recording the suspension of the member or lifting it.
+		/// Suspended members can neither propose nor commit votes
+		#[pallet::weight(T::WeightInfo::refresh_identity())]
+		pub fn refresh_identity(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			ensure!(Self::is_member(&who), Error::<T>::NotMember);
+			let suspended = <Suspensions<T>>::contains_key(&who);
+			if !Self::in_good_standing(&who) && !suspended {
+				let current_block = frame_system::Pallet::<T>::block_number();
+				<Suspensions<T>>::insert(&who, current_block);
+				Self::adjust_electorate(&who, false);
+				Self::deposit_event(Event::<T>::Suspended(who));
+			} else if Self::in_good_standing(&who) && suspended {
+				<Suspensions<T>>::remove(&who);
+				Self::adjust_electorate(&who, true);
+				Self::deposit_event(Event::<T>::Reinstated(who));
+			}
 
 			Ok(())
 		}
+
+		/// Lets the delegate vote with the voting tokens of the signer on the track,
+		/// or on every track if `None`. A delegation on a track takes precedence.
+		/// The delegators share the reward or the slash of the delegate's side
+		/// with their own stake, but are never penalised for the delegate not revealing
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			track: Option<TrackId>,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+
+			ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+			ensure!(to != signer && Self::is_member(&to), Error::<T>::InvalidDelegate);
+			if let Some(track) = track {
+				ensure!(Self::track(track).is_some(), Error::<T>::BadTrack);
+			}
+			//delegations are a single hop, so they can never form a cycle
+			ensure!(!Self::delegates_on(&to, track), Error::<T>::DelegationChain);
+			ensure!(!Self::represents_on(&signer, track), Error::<T>::DelegationChain);
+
+			Self::remove_delegation(&signer, track);
+			<Delegators<T>>::try_mutate(&to, track, |delegators| {
+				delegators.try_push(signer.clone())
+			})
+			.map_err(|_| Error::<T>::TooManyDelegators)?;
+			<Delegations<T>>::insert(&signer, track, to.clone());
+
+			Self::deposit_event(Event::<T>::Delegated { from: signer, to, track });
+			Ok(())
+		}
+
+		/// Ends the delegation of the signer on the track, or on every track if `None`.
+		/// Votes already committed by the delegate still count
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>, track: Option<TrackId>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+
+			ensure!(<Delegations<T>>::contains_key(&signer, track), Error::<T>::NotDelegating);
+			Self::remove_delegation(&signer, track);
+
+			Self::deposit_event(Event::<T>::Undelegated { who: signer, track });
+			Ok(())
+		}
+
+		/// Withdraws the proposal of the signer while nobody has committed to it.
+		/// The deposit is returned
+		#[pallet::weight(T::WeightInfo::cancel_proposal())]
+		pub fn cancel_proposal(origin: OriginFor<T>, proposal: T::Hash) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+
+			let proposal_data =
+				<ProposalData<T>>::get(&proposal).ok_or(Error::<T>::ProposalMissing)?;
+			ensure!(proposal_data.proposer == signer, Error::<T>::NotProposer);
+			ensure!(proposal_data.committed.is_empty(), Error::<T>::VotesCommitted);
+
+			Self::do_cancel(proposal, proposal_data, false)
+		}
+
+		/// Cancels the proposal in any phase. The voting tokens are refunded and nobody is slashed,
+		/// except for the deposit of a proposal cancelled as spam which is slashed
+		#[pallet::weight(T::WeightInfo::force_cancel(T::MaxVotersPerProposal::get()))]
+		pub fn force_cancel(origin: OriginFor<T>, proposal: T::Hash, spam: bool) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			let proposal_data =
+				<ProposalData<T>>::get(&proposal).ok_or(Error::<T>::ProposalMissing)?;
+			Self::do_cancel(proposal, proposal_data, spam)
+		}
+
+		/// Settles the votes of the member on a closed proposal: refunds the voting tokens,
+		/// slashes the losing side and non-revealers and pays the winners out of what was slashed.
+		/// Anyone can claim for a member, so the losing side cannot hold back the rewards
+		#[pallet::weight(T::WeightInfo::claim(T::MaxVotersPerProposal::get()))]
+		pub fn claim(origin: OriginFor<T>, proposal: T::Hash, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let settlement = <Settlements<T>>::get(&proposal).ok_or(Error::<T>::NothingToClaim)?;
+			let waiting = settlement.winners.iter().any(|(account, _)| account == &who);
+			let claimed = Self::do_claim(&who, proposal, settlement)?;
+			ensure!(claimed || !waiting, Error::<T>::SlashesPending);
+			ensure!(claimed, Error::<T>::NothingToClaim);
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Validates and stores a new proposal under the given hash
+	fn do_create_proposal(
+		signer: T::AccountId,
+		proposal_hash: T::Hash,
+		proposal_text: types::Data,
+		options: types::Options,
+		track: TrackId,
+		threshold: Threshold,
+		duration: T::BlockNumber,
+	) -> DispatchResult {
+		let track_info = Self::track(track).ok_or(Error::<T>::BadTrack)?;
+		if duration < track_info.min_length || duration > track_info.max_length {
+			ensure!(false, Error::<T>::WrongProposalLength);
+		}
+		ensure!(track_info.thresholds.contains(&threshold), Error::<T>::ThresholdNotAllowed);
+
+		//check if signer is a member already | tested
+		ensure!(Self::is_member(&signer), Error::<T>::NotMember);
+		ensure!(Self::in_good_standing(&signer), Error::<T>::Suspended);
+
+		// ensure that the track has a free slot
+		let active = <TrackProposals<T>>::get(track);
+		ensure!(active < track_info.max_proposals, Error::<T>::TrackFull);
+
+		// ensure that we don't have too many proposal
+		let length_res = <Proposals<T>>::decode_len();
+		if let Some(length) = length_res {
+			if length == T::MaxProposals::get() as usize {
+				ensure!(false, Error::<T>::TooManyProposals);
+			}
+		}
+
+		// ensure that proposal is not active, archived proposals can be submitted again
+		let (exist, _) = Self::proposal_exist(&proposal_hash);
+		ensure!(!exist, Error::<T>::DuplicateProposal);
+		//the claims and the funds of the pot of a closed proposal are kept by its hash
+		ensure!(
+			!<Settlements<T>>::contains_key(proposal_hash) &&
+				!<PotLedger<T>>::contains_key(proposal_hash),
+			Error::<T>::SettlementPending
+		);
+
+		// try to append, if error happens, this is probably we have too many proposals
+		ensure!(
+			<Proposals<T>>::try_append(proposal_hash).is_ok(),
+			Error::<T>::TooManyProposals
+		);
+
+		T::Currency::reserve_named(&Self::deposit_reserve_id(), &signer, track_info.deposit)?;
+		<TrackProposals<T>>::insert(track, active + 1);
+
+		// calculate the end block of proposal
+		let end = duration + frame_system::Pallet::<T>::block_number();
+
+		// construct the proposal object
+		let proposal = types::Proposal {
+			title: proposal_text,
+			proposer: signer.clone(),
+			track,
+			deposit: track_info.deposit,
+			ayes: 0,
+			nays: 0,
+			abstains: 0,
+			threshold,
+			tallies: vec![0; options.len()],
+			options,
+			poll_end: end,
+			length: duration,
+			reveal_end: None,
+			votes: Vec::new(),
+			convictions: Vec::new(),
+			revealed: Vec::new(),
+			committed: Vec::new(),
+			voters: 0,
+			electorate: <Members<T>>::count().saturating_sub(<Suspensions<T>>::count()),
+			members: <Members<T>>::count(),
+			last_eligible: <JoinCount<T>>::get(),
+			turnout: 0,
+			unrevealed: 0,
+		};
+
+		<ProposalData<T>>::insert(proposal_hash, proposal);
+		Self::schedule(end, Transition::CloseVote(proposal_hash))?;
+		Self::deposit_event(Event::<T>::Proposed {
+			account: signer,
+			proposal_hash,
+			deposit: track_info.deposit,
+		});
+
+		Ok(())
+	}
+
+	/// Closes the commit phase of the proposal and schedules the end of its reveal phase.
+	/// The keeper who triggered it, if any, is paid the bounty.
+	/// Returns the weight actually consumed
+	pub fn do_close_vote(
+		proposal: T::Hash,
+		keeper: Option<&T::AccountId>,
+	) -> Result<Weight, DispatchError> {
+		//ensure that proposal data exists
+		let proposal_data = <ProposalData<T>>::get(&proposal);
+		ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
+
+		//if we are here, then we know that data exists and we can unwrap it
+		let mut proposal_data = proposal_data.unwrap();
+
+		// if reveal end is set, then we know that voting phase ended
+		ensure!(proposal_data.reveal_end.is_none(), Error::<T>::VoteAlreadyEnded);
+
+		//make sure that we don't close voting phase too early
+		let current_block = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			proposal_data.poll_end <= current_block || proposal_data.all_committed(),
+			Error::<T>::TooEarly
+		);
+
+		// set the end of reveal phase, a proposal whose track has been removed
+		// reveals for as long as it was open to commits
+		let reveal_length = Self::track(proposal_data.track)
+			.map_or(proposal_data.length, |track| track.reveal_length);
+		let current_block = frame_system::Pallet::<T>::block_number();
+		let reveal_end = current_block + reveal_length;
+		proposal_data.reveal_end = Some(reveal_end);
+		Self::schedule(reveal_end, Transition::CloseReveal(proposal))?;
+
+		// the transition may be triggered before it is due
+		Self::unschedule(proposal_data.poll_end, &Transition::CloseVote(proposal));
+		if let Some(keeper) = keeper {
+			Self::pay_keeper(keeper, proposal, &mut proposal_data)?;
+		}
+		let weight = T::WeightInfo::close_vote(proposal_data.turnout);
+		let _ = <Turnout<T>>::drain_prefix(proposal).count();
+		let complete = proposal_data.all_revealed();
+		<ProposalData<T>>::insert(proposal, proposal_data);
+
+		Self::deposit_event(Event::<T>::ClosedCommit(proposal));
+		if complete {
+			Self::deposit_event(Event::<T>::RevealComplete(proposal));
+		}
+		Ok(weight)
+	}
+
+	/// Closes the reveal phase of the proposal, slashes and rewards the voters.
+	/// The keeper who triggered it, if any, is paid the bounty.
+	/// Returns the weight actually consumed
+	pub fn do_close_reveal(
+		proposal: T::Hash,
+		keeper: Option<&T::AccountId>,
+	) -> Result<Weight, DispatchError> {
+		//ensure that proposal data exists
+		let proposal_data = <ProposalData<T>>::get(&proposal);
+		ensure!(proposal_data.is_some(), Error::<T>::ProposalMissing);
+
+		//if we are here, then we know that data exists and we can unwrap it
+		let mut proposal_data = proposal_data.unwrap();
+
+		//if reveal phase end is not set, that means that we did not start it
+		ensure!(proposal_data.reveal_end.is_some(), Error::<T>::RevealNotStarted);
+
+		let reveal_end = proposal_data.reveal_end.unwrap();
+		let current_block = frame_system::Pallet::<T>::block_number();
+		ensure!(reveal_end <= current_block || proposal_data.all_revealed(), Error::<T>::TooEarly);
+		Self::unschedule(reveal_end, &Transition::CloseReveal(proposal));
+		if let Some(keeper) = keeper {
+			Self::pay_keeper(keeper, proposal, &mut proposal_data)?;
+		}
+
+		let revealed = proposal_data.revealed.len() as u32;
+		let mut weight = T::WeightInfo::close_reveal(revealed);
+
+		//deduce winning side, the voters claim their refunds, slashes and rewards later
+		let (outcome, margin, winners, losers) = Self::deduce_outcome(&proposal_data);
+		let losers = if outcome == Outcome::Tie && !T::SlashingPolicy::slash_on_tie() {
+			Vec::new()
+		} else {
+			losers
+		};
+		match outcome {
+			Outcome::Approved => {
+				Self::deposit_event(Event::<T>::Approved(proposal));
+			},
+			Outcome::Disapproved => {
+				Self::deposit_event(Event::<T>::Disapproved(proposal));
+			},
+			Outcome::Rejected => {
+				Self::deposit_event(Event::<T>::Rejected(proposal));
+			},
+			Outcome::Chosen(option) => {
+				Self::deposit_event(Event::<T>::Chosen { proposal_hash: proposal, option });
+			},
+			Outcome::Tie => {
+				Self::deposit_event(Event::<T>::Tie(proposal));
+			},
+			// nobody took a side that counts, what non-revealers lose stays in the pot
+			Outcome::NoQuorum => {
+				Self::deposit_event(Event::<T>::NoQuorum(proposal));
+			},
+			// never deduced, cancelled proposals are archived by `do_cancel`
+			Outcome::Cancelled => {},
+		}
+		// the call of a rejected proposal is never dispatched
+		let call = <ProposalCalls<T>>::take(&proposal).filter(|_| outcome == Outcome::Approved);
+
+		// the deposit is returned once the proposal reaches quorum,
+		// a proposal nobody revealed a vote on is treated as spam
+		let spam = proposal_data.revealed.is_empty() || outcome == Outcome::NoQuorum;
+		Self::settle_deposit(proposal, &proposal_data, spam)?;
+
+		//close proposal and free its slot
+		Self::open_settlement(proposal, &proposal_data, outcome, margin, winners, losers);
+		Self::archive(proposal, &proposal_data, outcome, current_block);
+
+		Self::deposit_event(Event::<T>::ClosedReveal {
+			proposal_hash: proposal,
+			yes: proposal_data.ayes,
+			no: proposal_data.nays,
+			abstain: proposal_data.abstains,
+			revealed: proposal_data.revealed.len() as u32,
+		});
+
+		// dispatched last, so the call finds the proposal closed and settled
+		if let Some(call) = call {
+			weight = weight.saturating_add(call.get_dispatch_info().weight);
+			let result = call
+				.dispatch(RawOrigin::Approved(proposal).into())
+				.map(|_| ())
+				.map_err(|e| e.error);
+			<Archive<T>>::mutate(proposal, current_block, |record| {
+				if let Some(record) = record {
+					record.executed = Some(result);
+				}
+			});
+			Self::deposit_event(Event::<T>::Executed { proposal_hash: proposal, result });
+		}
+		Ok(weight)
+	}
+
+	/// Records what the voters of the closed proposal have to claim.
+	/// The shares of the winners are taken now, the slashes once the voters claim
+	fn open_settlement(
+		proposal: T::Hash,
+		proposal_data: &ProposalOf<T>,
+		outcome: Outcome,
+		margin: u32,
+		winners: Vec<(T::AccountId, VoteToken)>,
+		losers: Vec<(T::AccountId, VoteToken)>,
+	) {
+		let mut winners: Vec<(T::AccountId, BalanceOf<T>)> = winners
+			.into_iter()
+			.map(|(who, weight)| {
+				let stake = Self::stake_of(&who);
+				let share = T::SlashingPolicy::share(&Voter { who: who.clone(), weight, stake });
+				(who, share)
+			})
+			.collect();
+		//a tie pays the pool to the proposer
+		if outcome == Outcome::Tie {
+			winners = vec![(proposal_data.proposer.clone(), One::one())];
+		}
+		let mut shares = winners
+			.iter()
+			.fold(BalanceOf::<T>::zero(), |total, (_, share)| total.saturating_add(*share));
+		//winners with nothing to weigh by split the pool equally
+		if shares.is_zero() {
+			winners.iter_mut().for_each(|(_, share)| *share = One::one());
+			shares = (winners.len() as u32).into();
+		}
+
+		let votes: Vec<Claim<T::AccountId>> = proposal_data
+			.votes
+			.iter()
+			.map(|(who, number, _)| Claim {
+				who: who.clone(),
+				number: *number,
+				lost: losers.iter().find(|(loser, _)| loser == who).map(|(_, weight)| *weight),
+				conviction: proposal_data.conviction_of(who),
+			})
+			.collect();
+		let unrevealed: Vec<T::AccountId> = proposal_data
+			.committed
+			.iter()
+			.filter(|who| !proposal_data.revealed.contains(who))
+			.cloned()
+			.collect();
+
+		let mut settlement = Settlement {
+			margin,
+			closed_at: frame_system::Pallet::<T>::block_number(),
+			length: proposal_data.length,
+			multi_option: proposal_data.is_multi_option(),
+			votes,
+			unrevealed,
+			winners,
+			shares,
+			pool: Zero::zero(),
+		};
+		//the leftovers of earlier proposals are added to the reward of those who won the vote
+		if outcome != Outcome::Tie && !settlement.winners.is_empty() {
+			settlement.pool = <CarryOver<T>>::take();
+			<PotLedger<T>>::insert(proposal, settlement.pool);
+		}
+		//nobody is slashed and nothing was carried over, so there is nothing to pay out
+		if !settlement.slashes_pending() && settlement.pool.is_zero() {
+			settlement.winners.clear();
+			<PotLedger<T>>::remove(proposal);
+		} else if outcome == Outcome::Tie {
+			<PendingClaims<T>>::insert(&proposal_data.proposer, proposal, ());
+		}
+		if !settlement.is_settled() {
+			<Settlements<T>>::insert(proposal, settlement);
+		}
+	}
+
+	/// Settles what the account has left to claim on the closed proposal.
+	/// A member who left or was removed is dropped from the settlement without effect.
+	/// Returns whether anything was settled, the rewards wait until every slash is in
+	fn do_claim(
+		who: &T::AccountId,
+		proposal: T::Hash,
+		mut settlement: Settlement<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	) -> Result<bool, DispatchError> {
+		let member = Self::is_member(who);
+		let mut claimed = false;
+		let mut slashed = BalanceOf::<T>::zero();
+		//the part of the slashes the winners can be paid out of
+		let mut credited = BalanceOf::<T>::zero();
+		let mut reward = BalanceOf::<T>::zero();
+
+		if let Some(index) = settlement.votes.iter().position(|claim| &claim.who == who) {
+			let claim = settlement.votes.swap_remove(index);
+			if member {
+				Self::deposit_votes(who, settlement.cost(claim.number));
+				if let Some(weight) = claim.lost {
+					let voter = vec![(who.clone(), weight)];
+					let (lost, to_source) = Self::slash_voting_side(voter, settlement.margin)?;
+					slashed = lost;
+					credited = to_source;
+				}
+				Self::lock_conviction(who, claim.conviction, &settlement);
+			}
+			claimed = true;
+		}
+
+		if let Some(index) = settlement.unrevealed.iter().position(|account| account == who) {
+			settlement.unrevealed.swap_remove(index);
+			//the delegators are not punished for the delegate
+			Self::refund_delegated_votes(&proposal, who, settlement.multi_option);
+			//already revealed late and slashed
+			if let Some(commit) = <Commits<T>>::take(who, &proposal) {
+				if member {
+					if T::RefundNonRevealed::get() {
+						Self::deposit_votes(who, settlement.cost(commit.number));
+					}
+					let penalty = T::NonRevealPenalty::get() * Self::stake_of(who);
+					let (penalised, penalty_credited) = Self::slash_stake(who, penalty)?;
+					slashed = slashed.saturating_add(penalised);
+					credited = credited.saturating_add(penalty_credited);
+					Self::deposit_event(Event::<T>::NonRevealed {
+						who: who.clone(),
+						slashed: penalised,
+					});
+				}
+			}
+			claimed = true;
+		}
+		settlement.pool = settlement.pool.saturating_add(credited);
+		<PotLedger<T>>::mutate(proposal, |held| *held = held.saturating_add(credited));
+
+		if let Some(index) = settlement.winners.iter().position(|(account, _)| account == who) {
+			if !member || !settlement.slashes_pending() {
+				let (_, share) = settlement.winners.swap_remove(index);
+				if member {
+					//never pay out more than the pool
+					reward = Perbill::from_rational(share, settlement.shares) * settlement.pool;
+					reward = Self::reward_account(who, reward)?;
+					<PotLedger<T>>::mutate(proposal, |held| *held = held.saturating_sub(reward));
+				} else {
+					//the pool is split among the other winners
+					settlement.shares = settlement.shares.saturating_sub(share);
+				}
+				claimed = true;
+			}
+		}
+
+		if !claimed {
+			return Ok(false)
+		}
+		if !settlement.winners.iter().any(|(account, _)| account == who) {
+			<PendingClaims<T>>::remove(who, &proposal);
+		}
+		if settlement.is_settled() {
+			<Settlements<T>>::remove(&proposal);
+			<Archive<T>>::mutate(proposal, settlement.closed_at, |record| {
+				if let Some(record) = record {
+					record.payout = settlement.pool;
+				}
+			});
+			let payout = settlement.pool;
+			Self::deposit_event(Event::<T>::Settled { proposal_hash: proposal, payout });
+			//the rounding dust and the slashes nobody was rewarded with
+			Self::release_leftover(<PotLedger<T>>::take(proposal))?;
+		} else {
+			<Settlements<T>>::insert(proposal, settlement);
+		}
+		if member {
+			Self::deposit_event(Event::<T>::Claimed {
+				who: who.clone(),
+				proposal_hash: proposal,
+				slashed,
+				reward,
+			});
+		}
+		Ok(true)
+	}
+
+	/// Claims on the closed proposals the member voted on, at most `MaxProposals` at once.
+	/// Returns the number of proposals claimed on
+	fn claim_closed(who: &T::AccountId) -> Result<u32, DispatchError> {
+		let closed: Vec<(T::Hash, Settlement<T::AccountId, T::BlockNumber, BalanceOf<T>>)> =
+			<PendingClaims<T>>::iter_key_prefix(who)
+				.filter_map(|proposal| Some((proposal, <Settlements<T>>::get(proposal)?)))
+				.take(T::MaxProposals::get() as usize)
+				.collect();
+		let count = closed.len() as u32;
+		for (proposal, settlement) in closed {
+			Self::do_claim(who, proposal, settlement)?;
+		}
+		Ok(count)
+	}
+
+	/// The weight of claiming on the given number of closed proposals
+	pub fn claims_weight(count: u32) -> Weight {
+		T::WeightInfo::claim(T::MaxVotersPerProposal::get()).saturating_mul(count as Weight)
+	}
+
+	/// Reserves the stake of the account and gives it the voting tokens of a member
+	fn do_join(who: T::AccountId) -> DispatchResult {
+		//check if the account has enough money to deposit
+		ensure!(
+			T::Currency::can_reserve(&who, T::BasicDeposit::get()),
+			Error::<T>::NotEnoughFunds
+		);
+
+		T::Currency::reserve_named(&Self::reserve_id(), &who, T::BasicDeposit::get())?;
+
+		//deposit 100 voting tokens to the voter
+		Self::deposit_votes(&who, T::MaxVotingTokens::get());
+
+		//reserve the fixed amount specified in the config
+		Self::set_reserved_balance(&who, T::BasicDeposit::get());
+
+		//members who join later are not waited for on the active proposals
+		let index = <JoinCount<T>>::mutate(|count| {
+			*count = count.saturating_add(1);
+			*count
+		});
+		<JoinIndex<T>>::insert(&who, index);
+
+		Self::deposit_event(Event::<T>::Joined(who));
+
+		Ok(())
+	}
+
+	/// Removes the member, withdrawing the commits and votes on active proposals,
+	/// so the member is neither rewarded nor slashed for them.
+	/// The votes on closed proposals are claimed first, the rewards still pending are given up.
+	/// The given share of the stake goes to [`Config::Slash`] and the rest is released
+	fn do_kick(who: &T::AccountId, forfeit: Perbill) -> DispatchResult {
+		Self::claim_closed(who)?;
+		let _ = <PendingClaims<T>>::drain_prefix(who).count();
+		let _ = <Commits<T>>::drain_prefix(who).count();
+		for proposal in <Proposals<T>>::get() {
+			<ProposalData<T>>::mutate(proposal, |proposal_data| {
+				if let Some(proposal_data) = proposal_data {
+					if proposal_data.committed.contains(who) {
+						proposal_data.voters = proposal_data.voters.saturating_sub(1);
+					}
+					proposal_data.withdraw_vote(who);
+					//the voting tokens of the member and its delegators are no longer committed
+					let delegators = <DelegatedVotes<T>>::get(&proposal, who);
+					proposal_data.voters =
+						proposal_data.voters.saturating_sub(delegators.len() as u32);
+					let accounts = delegators.iter().map(|(delegator, _)| delegator);
+					for account in accounts.chain(sp_std::iter::once(who)) {
+						if <Turnout<T>>::take(&proposal, account).is_some() {
+							proposal_data.turnout = proposal_data.turnout.saturating_sub(1);
+						}
+					}
+					Self::refund_delegated_votes(&proposal, who, proposal_data.is_multi_option());
+				}
+			});
+		}
+		Self::clear_delegations(who);
+
+		let penalty = forfeit * Self::stake_of(who);
+		let (imbalance, _) = T::Currency::slash_reserved_named(&Self::reserve_id(), who, penalty);
+		let forfeited = imbalance.peek();
+		let credited = Self::handle_slash(imbalance);
+		Self::release_leftover(credited)?;
+		let reserved_balance = <Members<T>>::get(who).reserved_balance.saturating_sub(forfeited);
+		T::Currency::unreserve_named(&Self::reserve_id(), who, reserved_balance);
+		//its turnout is already taken
+		if !<Suspensions<T>>::contains_key(who) {
+			Self::adjust_electorate(who, false);
+		}
+		<Members<T>>::remove(who);
+		<Suspensions<T>>::remove(who);
+		<ForceAdded<T>>::remove(who);
+		<ConvictionLocks<T>>::remove(who);
+		<JoinIndex<T>>::remove(who);
+
+		Self::deposit_event(Event::<T>::Kicked { who: who.clone(), forfeited });
+		Ok(())
+	}
+
+	/// Stops the proposal in any phase, refunds the voting tokens of everyone who committed
+	/// and archives the proposal as cancelled
+	pub fn do_cancel(
+		proposal: T::Hash,
+		proposal_data: ProposalOf<T>,
+		spam: bool,
+	) -> DispatchResult {
+		match proposal_data.reveal_end {
+			Some(reveal_end) => Self::unschedule(reveal_end, &Transition::CloseReveal(proposal)),
+			None => Self::unschedule(proposal_data.poll_end, &Transition::CloseVote(proposal)),
+		}
+
+		//the commits of revealed votes are already gone
+		for (account, number, _) in proposal_data.votes.iter() {
+			Self::deposit_votes(account, proposal_data.cost(*number));
+			<PendingClaims<T>>::remove(account, &proposal);
+		}
+		for who in proposal_data.committed.iter() {
+			<PendingClaims<T>>::remove(who, &proposal);
+			if let Some(commit) = <Commits<T>>::take(who, &proposal) {
+				Self::deposit_votes(who, proposal_data.cost(commit.number));
+				let multi_option = proposal_data.is_multi_option();
+				Self::refund_delegated_votes(&proposal, who, multi_option);
+			}
+		}
+		<ProposalCalls<T>>::remove(&proposal);
+		let _ = <Turnout<T>>::drain_prefix(proposal).count();
+
+		Self::settle_deposit(proposal, &proposal_data, spam)?;
+		let current_block = frame_system::Pallet::<T>::block_number();
+		Self::archive(proposal, &proposal_data, Outcome::Cancelled, current_block);
+
+		Self::deposit_event(Event::<T>::Cancelled(proposal));
+		Ok(())
+	}
+
+	/// Replaces the data of a closed proposal with a compact record of its results
+	/// and frees the slot of the proposal
+	fn archive(
+		proposal_hash: T::Hash,
+		proposal: &types::Proposal<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+		outcome: Outcome,
+		closed_at: T::BlockNumber,
+	) {
+		<Proposals<T>>::mutate(|proposals| proposals.retain(|hash| hash != &proposal_hash));
+		<ProposalData<T>>::remove(proposal_hash);
+		<TrackProposals<T>>::mutate(proposal.track, |active| *active = active.saturating_sub(1));
+		let record = ArchivedProposal {
+			proposer: proposal.proposer.clone(),
+			track: proposal.track,
+			outcome,
+			ayes: proposal.ayes,
+			nays: proposal.nays,
+			abstains: proposal.abstains,
+			tallies: proposal.tallies.clone(),
+			revealed: proposal.revealed.len() as u32,
+			payout: Zero::zero(),
+			closed_at,
+			executed: None,
+		};
+		<Archive<T>>::insert(proposal_hash, closed_at, record);
+	}
+
+	/// Returns the deposit of the proposal to the proposer or slashes it to [`Config::Slash`]
+	fn settle_deposit(
+		proposal_hash: T::Hash,
+		proposal: &ProposalOf<T>,
+		slash: bool,
+	) -> DispatchResult {
+		if proposal.deposit.is_zero() {
+			return Ok(())
+		}
+		let who = proposal.proposer.clone();
+		if slash {
+			let (imbalance, _) = T::Currency::slash_reserved_named(
+				&Self::deposit_reserve_id(),
+				&who,
+				proposal.deposit,
+			);
+			let amount = imbalance.peek();
+			let credited = Self::handle_slash(imbalance);
+			Self::deposit_event(Event::<T>::DepositSlashed { proposal_hash, who, amount });
+			Self::release_leftover(credited)?;
+		} else {
+			let leftover =
+				T::Currency::unreserve_named(&Self::deposit_reserve_id(), &who, proposal.deposit);
+			let amount = proposal.deposit.saturating_sub(leftover);
+			Self::deposit_event(Event::<T>::DepositRefunded { proposal_hash, who, amount });
+		}
+		Ok(())
+	}
+
+	/// Pays the keeper [`Config::KeeperBounty`] out of the deposit of the proposer,
+	/// and whatever the deposit lacks out of the carry-over of the pot
+	fn pay_keeper(
+		keeper: &T::AccountId,
+		proposal: T::Hash,
+		proposal_data: &mut ProposalOf<T>,
+	) -> DispatchResult {
+		let bounty = T::KeeperBounty::get();
+		if bounty.is_zero() {
+			return Ok(())
+		}
+		let owed = bounty.min(proposal_data.deposit);
+		let lost = T::Currency::repatriate_reserved_named(
+			&Self::deposit_reserve_id(),
+			&proposal_data.proposer,
+			keeper,
+			owed,
+			frame_support::traits::BalanceStatus::Free,
+		)?;
+		let from_deposit = owed.saturating_sub(lost);
+		proposal_data.deposit = proposal_data.deposit.saturating_sub(from_deposit);
+
+		//the funds held for closed proposals are never touched
+		let owed = bounty.saturating_sub(from_deposit).min(<CarryOver<T>>::get());
+		let imbalance = T::RewardSource::withdraw(owed);
+		let from_pot = imbalance.peek();
+		T::Currency::resolve_creating(keeper, imbalance);
+		<CarryOver<T>>::mutate(|carried| *carried = carried.saturating_sub(from_pot));
+
+		Self::deposit_event(Event::<T>::KeeperPaid {
+			who: keeper.clone(),
+			proposal_hash: proposal,
+			bounty: from_deposit.saturating_add(from_pot),
+		});
+		Ok(())
+	}
+
+	/// Schedules the phase transition of a proposal at the given block
+	fn schedule(block: T::BlockNumber, transition: Transition<T::Hash>) -> DispatchResult {
+		<Schedule<T>>::try_append(block, transition).map_err(|_| Error::<T>::TooManyProposals)?;
+		Ok(())
+	}
+
+	/// Removes the phase transition of a proposal scheduled at the given block,
+	/// or deferred to the next block if it is overdue
+	fn unschedule(block: T::BlockNumber, transition: &Transition<T::Hash>) {
+		let next = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+		let mut blocks = vec![block];
+		if block < next {
+			blocks.push(next);
+		}
+		for block in blocks {
+			<Schedule<T>>::mutate_exists(block, |due| {
+				if let Some(transitions) = due {
+					transitions.retain(|t| t != transition);
+					if transitions.is_empty() {
+						*due = None;
+					}
+				}
+			});
+		}
+	}
+
+	/// Defers the transitions left over by a busy block to the next block.
+	/// Every active proposal has a single transition scheduled, so they always fit
+	fn defer(now: T::BlockNumber, transitions: Vec<Transition<T::Hash>>) {
+		<Schedule<T>>::mutate(now.saturating_add(One::one()), |due| {
+			for transition in transitions {
+				let _ = due.try_push(transition);
+			}
+		});
+	}
+
+	/// The weight the transition may consume at most, the call of the proposal included
+	fn transition_weight(transition: &Transition<T::Hash>) -> Weight {
+		let voters = T::MaxVotersPerProposal::get();
+		match transition {
+			Transition::CloseVote(_) => T::WeightInfo::close_vote(voters),
+			Transition::CloseReveal(proposal) => {
+				let call = <ProposalCalls<T>>::get(proposal)
+					.map_or(0, |call| call.get_dispatch_info().weight);
+				T::WeightInfo::close_reveal(voters).saturating_add(call)
+			},
+		}
+	}
+
+	/// Applies the scheduled phase transition and returns the weight it consumed,
+	/// reverting all its changes on failure
+	fn apply_transition(transition: Transition<T::Hash>) -> Result<Weight, DispatchError> {
+		with_transaction(|| {
+			let result = match transition {
+				Transition::CloseVote(proposal) => Self::do_close_vote(proposal, None),
+				Transition::CloseReveal(proposal) => Self::do_close_reveal(proposal, None),
+			};
+			if result.is_ok() {
+				TransactionOutcome::Commit(result)
+			} else {
+				TransactionOutcome::Rollback(result)
+			}
+		})
+	}
+
+	/// The phase of the proposal, `None` if the proposal is unknown
+	pub fn phase(proposal: &T::Hash) -> Option<Phase<T::BlockNumber>> {
+		match <ProposalData<T>>::get(proposal) {
+			Some(data) => Some(match data.reveal_end {
+				Some(ends) => Phase::Reveal { ends },
+				None => Phase::Commit { ends: data.poll_end },
+			}),
+			None => <Archive<T>>::iter_key_prefix(proposal).max().map(|at| Phase::Closed { at }),
+		}
+	}
+
+	/// The archived results of the proposal, once for every time it was closed
+	pub fn archived(
+		proposal: &T::Hash,
+	) -> Vec<ArchivedProposal<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+		<Archive<T>>::iter_prefix_values(proposal).collect()
+	}
+
+	/// The stake and voting tokens of the member, `None` if the account is not a member
+	pub fn member(who: &T::AccountId) -> Option<VoterBalance<BalanceOf<T>>> {
+		if !Self::is_member(who) {
+			return None
+		}
+		let info = <Members<T>>::get(who);
+		//a member may delegate to the same delegate on every track and on some track
+		let mut delegators: Vec<T::AccountId> =
+			<Delegators<T>>::iter_prefix_values(who).flatten().collect();
+		delegators.sort();
+		delegators.dedup();
+		let delegated_in = delegators
+			.iter()
+			.map(|delegator| <Members<T>>::get(delegator).voting_tokens as u32)
+			.sum();
+		let delegated_out = if <Delegations<T>>::iter_key_prefix(who).next().is_some() {
+			info.voting_tokens
+		} else {
+			0
+		};
+		Some(VoterBalance {
+			voting_tokens: info.voting_tokens,
+			reserved_balance: info.reserved_balance,
+			delegated_in,
+			delegated_out,
+		})
+	}
+
+	/// The proposals the member has committed to and not revealed yet
+	pub fn pending_reveals(who: &T::AccountId) -> Vec<T::Hash> {
+		//the commits left on closed proposals are settled by claiming
+		<Commits<T>>::iter_key_prefix(who)
+			.filter(|proposal| !<Settlements<T>>::contains_key(proposal))
+			.collect()
+	}
+
+	/// The closed proposals the member has votes or rewards to claim on
+	pub fn pending_claims(who: &T::AccountId) -> Vec<T::Hash> {
+		<PendingClaims<T>>::iter_key_prefix(who)
+			.filter(|proposal| <Settlements<T>>::contains_key(proposal))
+			.collect()
+	}
+
 	pub fn is_member(who: &T::AccountId) -> bool {
 		<Members<T>>::contains_key(who)
 	}
@@ -583,6 +1893,29 @@ impl<T: Config> Pallet<T> {
 		<Commits<T>>::get(who, proposal_hash).is_some()
 	}
 
+	/// Computes the commitment of a vote, i.e. the hash of the latest [`types::CommitPayload`].
+	/// The payload is bound to the pallet, the chain, the proposal, the voter and the weight,
+	/// so the commitment can not be replayed anywhere else.
+	/// The secret salt must be random, otherwise the vote can be brute forced from the commitment
+	pub fn commitment_of(
+		who: &T::AccountId,
+		proposal_hash: &T::Hash,
+		vote: &types::Vote,
+		number: types::VoteToken,
+		salt: &[u8; 32],
+	) -> T::Hash {
+		let payload = types::CommitPayload::V1 {
+			domain: T::PalletId::get().0,
+			genesis_hash: frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero()),
+			proposal_hash: *proposal_hash,
+			number,
+			voter: who.clone(),
+			vote: vote.clone(),
+			salt: *salt,
+		};
+		T::Hashing::hash_of(&payload)
+	}
+
 	/// Deposit voting tokens to the account and make sure it does not exceed the limit
 	pub fn deposit_votes(who: &T::AccountId, tokens: u8) {
 		<Members<T>>::mutate(who, |balance| {
@@ -600,6 +1933,196 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// The stake of the member that the pallet can act on
+	pub fn stake_of(who: &T::AccountId) -> BalanceOf<T> {
+		<Members<T>>::get(who).reserved_balance
+	}
+
+	/// The block until which the stake of the member is locked by conviction votes
+	pub fn locked_until(who: &T::AccountId) -> Option<T::BlockNumber> {
+		<ConvictionLocks<T>>::get(who)
+	}
+
+	/// Locks the stake of a voter who revealed with conviction from the close of the proposal,
+	/// extending any lock it already has
+	fn lock_conviction(
+		who: &T::AccountId,
+		conviction: Conviction,
+		settlement: &Settlement<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	) {
+		if conviction == Conviction::None {
+			return
+		}
+		let periods: T::BlockNumber = conviction.lock_periods().into();
+		let until = settlement.closed_at.saturating_add(settlement.length.saturating_mul(periods));
+		<ConvictionLocks<T>>::mutate(who, |lock| {
+			*lock = Some(lock.map_or(until, |current| current.max(until)));
+		});
+	}
+
+	/// The delegate the member votes through on the track
+	pub fn delegate_of(who: &T::AccountId, track: TrackId) -> Option<T::AccountId> {
+		<Delegations<T>>::get(who, Some(track)).or_else(|| <Delegations<T>>::get(who, None))
+	}
+
+	/// The members voting through the delegate on the track
+	pub fn delegators_of(delegate: &T::AccountId, track: TrackId) -> Vec<T::AccountId> {
+		let mut delegators = <Delegators<T>>::get(delegate, Some(track)).into_inner();
+		//a delegation on the track overrides the one on every track
+		for delegator in <Delegators<T>>::get(delegate, None) {
+			if !delegators.contains(&delegator) &&
+				Self::delegate_of(&delegator, track).as_ref() == Some(delegate)
+			{
+				delegators.push(delegator);
+			}
+		}
+		delegators
+	}
+
+	/// Whether the member delegates on the track, or on any track if `None`
+	fn delegates_on(who: &T::AccountId, track: Option<TrackId>) -> bool {
+		match track {
+			Some(track) => Self::delegate_of(who, track).is_some(),
+			None => <Delegations<T>>::iter_key_prefix(who).next().is_some(),
+		}
+	}
+
+	/// Whether anyone delegates to the member on the track, or on any track if `None`
+	fn represents_on(who: &T::AccountId, track: Option<TrackId>) -> bool {
+		match track {
+			Some(track) =>
+				!<Delegators<T>>::get(who, Some(track)).is_empty() ||
+					!<Delegators<T>>::get(who, None).is_empty(),
+			None =>
+				<Delegators<T>>::iter_prefix_values(who).any(|delegators| !delegators.is_empty()),
+		}
+	}
+
+	/// Ends the delegation of the member on the track, or on every track if `None`
+	fn remove_delegation(who: &T::AccountId, track: Option<TrackId>) {
+		if let Some(delegate) = <Delegations<T>>::take(who, track) {
+			<Delegators<T>>::mutate(&delegate, track, |delegators| {
+				delegators.retain(|delegator| delegator != who)
+			});
+		}
+	}
+
+	/// Ends the delegations of the member and those to the member
+	fn clear_delegations(who: &T::AccountId) {
+		let tracks: Vec<Option<TrackId>> = <Delegations<T>>::iter_key_prefix(who).collect();
+		for track in tracks {
+			Self::remove_delegation(who, track);
+		}
+		for (track, delegators) in <Delegators<T>>::drain_prefix(who) {
+			for delegator in delegators {
+				<Delegations<T>>::remove(&delegator, track);
+			}
+		}
+	}
+
+	/// Spends the voting tokens of the delegators of the member on the track of the proposal,
+	/// so the commit of the member carries a vote of the same number for each of them.
+	/// Delegators beyond `MaxVotersPerProposal` do not follow
+	fn commit_delegated_votes(
+		delegate: &T::AccountId,
+		proposal: &T::Hash,
+		proposal_data: &mut ProposalOf<T>,
+		number: VoteToken,
+	) {
+		let mut carried = Vec::new();
+		for delegator in Self::delegators_of(delegate, proposal_data.track) {
+			if proposal_data.voters >= T::MaxVotersPerProposal::get() {
+				break
+			}
+			//those who committed before delegating and suspended members do not follow
+			let committed = <Commits<T>>::contains_key(&delegator, proposal);
+			if committed || !Self::in_good_standing(&delegator) {
+				continue
+			}
+			let tokens = <Members<T>>::get(&delegator).voting_tokens;
+			let delegated = proposal_data.delegated_number(number, tokens);
+			if delegated > 0 && Self::decrease_votes(&delegator, proposal_data.cost(delegated)) {
+				proposal_data.voters += 1;
+				Self::count_turnout(&delegator, proposal, proposal_data);
+				carried.push((delegator, delegated));
+			}
+		}
+		if !carried.is_empty() {
+			<DelegatedVotes<T>>::insert(proposal, delegate, carried);
+		}
+	}
+
+	/// Counts the voting tokens of the member committed on the proposal towards its turnout,
+	/// once and only if the member was in the committee when the proposal was created
+	fn count_turnout(who: &T::AccountId, proposal: &T::Hash, proposal_data: &mut ProposalOf<T>) {
+		let eligible = <JoinIndex<T>>::get(who) <= proposal_data.last_eligible;
+		if eligible && !<Turnout<T>>::contains_key(proposal, who) {
+			<Turnout<T>>::insert(proposal, who, ());
+			proposal_data.turnout += 1;
+		}
+	}
+
+	/// Adds the member to or removes it from the electorate of the proposals in their commit phase
+	/// it is eligible for, unless its voting tokens are already counted in their turnout
+	fn adjust_electorate(who: &T::AccountId, add: bool) {
+		let index = <JoinIndex<T>>::get(who);
+		for proposal in <Proposals<T>>::get() {
+			if <Turnout<T>>::contains_key(&proposal, who) {
+				continue
+			}
+			<ProposalData<T>>::mutate(proposal, |proposal_data| {
+				if let Some(proposal_data) = proposal_data {
+					if proposal_data.reveal_end.is_none() && index <= proposal_data.last_eligible {
+						proposal_data.electorate = if add {
+							proposal_data.electorate.saturating_add(1)
+						} else {
+							proposal_data.electorate.saturating_sub(1)
+						};
+					}
+				}
+			});
+		}
+	}
+
+	/// Gives the delegators back the voting tokens spent on the unrevealed commit of the delegate
+	fn refund_delegated_votes(proposal: &T::Hash, delegate: &T::AccountId, multi_option: bool) {
+		for (delegator, number) in <DelegatedVotes<T>>::take(proposal, delegate) {
+			let cost = if multi_option { number } else { number.saturating_pow(2) };
+			if Self::is_member(&delegator) {
+				Self::deposit_votes(&delegator, cost);
+			}
+		}
+	}
+
+	/// Whether the identity of the account meets the minimum judgement
+	pub fn is_judged(who: &T::AccountId) -> bool {
+		T::IdentityProvider::judgement(who) >= T::MinJudgement::get()
+	}
+
+	/// Whether the member can propose and commit votes,
+	/// i.e. its identity is judged good enough or it was added by force
+	pub fn in_good_standing(who: &T::AccountId) -> bool {
+		<ForceAdded<T>>::contains_key(who) || Self::is_judged(who)
+	}
+
+	/// The parameters of the track with the given id
+	pub fn track(id: TrackId) -> Option<TrackOf<T>> {
+		T::Tracks::get().into_iter().find(|(track, _)| *track == id).map(|(_, info)| info)
+	}
+
+	/// The id under which the committee stake and the pot are reserved
+	pub fn reserve_id() -> [u8; 8] {
+		T::PalletId::get().0
+	}
+
+	/// The id under which the proposal deposits are reserved,
+	/// the pallet id with its last two bytes replaced by `/d`
+	pub fn deposit_reserve_id() -> [u8; 8] {
+		let mut id = T::PalletId::get().0;
+		id[6..].copy_from_slice(b"/d");
+		id
+	}
+
 	/// tries to decrease the voting tokens of a specific account by specified amount.
 	/// Returns false if account does not have enough voting tokens
 	pub fn decrease_votes(who: &T::AccountId, amount: u8) -> bool {
@@ -613,56 +2136,251 @@ impl<T: Config> Pallet<T> {
 		.is_ok()
 	}
 
-	/// Slashes the losing side, puts money in a pot and returns the total amount slashed
+	/// Deduces the outcome of the proposal, the margin of the winning side
+	/// and who is on the winning and the losing side with the number of votes they gave to it.
+	/// On a tie there are no winners and every voter is on the losing side,
+	/// without quorum or with a majority short of the threshold there is neither side
+	#[allow(clippy::type_complexity)]
+	fn deduce_outcome(
+		proposal_data: &ProposalOf<T>,
+	) -> (Outcome, u32, Vec<(T::AccountId, VoteToken)>, Vec<(T::AccountId, VoteToken)>) {
+		//abstainers are never on either side
+		let everyone = || -> Vec<(T::AccountId, VoteToken)> {
+			proposal_data
+				.votes
+				.iter()
+				.filter(|entry| entry.2 != Vote::Abstain)
+				.map(|entry| (entry.0.clone(), entry.1))
+				.collect()
+		};
+		if !Self::quorum_reached(proposal_data) {
+			return (Outcome::NoQuorum, 0, Vec::new(), Vec::new())
+		}
+
+		if !proposal_data.is_multi_option() {
+			let (ayes, nays) = (proposal_data.ayes, proposal_data.nays);
+			let margin = ayes.abs_diff(nays);
+			if proposal_data.threshold.approves(ayes, nays) {
+				return (
+					Outcome::Approved,
+					margin,
+					Self::voters_of(proposal_data, &Vote::Yes),
+					Self::voters_of(proposal_data, &Vote::No),
+				)
+			}
+			//a majority short of the threshold rejects the proposal without being punished for it
+			return match ayes.cmp(&nays) {
+				Ordering::Equal => (Outcome::Tie, 0, Vec::new(), everyone()),
+				Ordering::Greater => (Outcome::Rejected, margin, Vec::new(), Vec::new()),
+				Ordering::Less => (
+					Outcome::Disapproved,
+					margin,
+					Self::voters_of(proposal_data, &Vote::No),
+					Self::voters_of(proposal_data, &Vote::Yes),
+				),
+			}
+		}
+
+		//several options sharing the most votes is a tie
+		let top = proposal_data.tallies.iter().copied().max().unwrap_or_default();
+		let mut leaders = proposal_data.tallies.iter().enumerate().filter(|(_, tally)| **tally == top);
+		let winner = match (leaders.next(), leaders.next()) {
+			(Some((index, _)), None) => index,
+			_ => return (Outcome::Tie, 0, Vec::new(), everyone()),
+		};
+		let runner_up = proposal_data
+			.tallies
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| *index != winner)
+			.map(|(_, tally)| *tally)
+			.max()
+			.unwrap_or_default();
+		let total: u32 = proposal_data.tallies.iter().sum();
+		let consensus = proposal_data.threshold == Threshold::SimpleMajority ||
+			proposal_data.threshold.approves(top, total - top);
+		//as with yes or no, a leader short of the threshold is not punished for it
+		if !consensus {
+			return (Outcome::Rejected, top - runner_up, Vec::new(), Vec::new())
+		}
+
+		//voters who gave any votes to the winning option are on the winning side
+		let winner = winner as OptionIndex;
+		let mut winners = Vec::new();
+		let mut losers = Vec::new();
+		for (account, number, vote) in proposal_data.votes.iter() {
+			if *vote == Vote::Abstain {
+				continue
+			}
+			//both sides weigh the tokens given, not their cost
+			let (given, spread) = match vote {
+				Vote::Options(choices) => (
+					choices.iter().find(|(option, _)| *option == winner).map(|(_, tokens)| *tokens),
+					choices
+						.iter()
+						.fold(0, |total: VoteToken, (_, tokens)| total.saturating_add(*tokens)),
+				),
+				_ => (None, *number),
+			};
+			match given {
+				Some(tokens) => winners.push((account.clone(), tokens)),
+				None => losers.push((account.clone(), spread)),
+			}
+		}
+		(Outcome::Chosen(winner), top - runner_up, winners, losers)
+	}
+
+	/// Whether enough votes were revealed for the results of the proposal to count.
+	/// A quorum by members is a share of the committee when the proposal was created.
+	/// A proposal whose track has been removed never reaches quorum
+	fn quorum_reached(proposal_data: &ProposalOf<T>) -> bool {
+		let quorum = match Self::track(proposal_data.track) {
+			Some(track) => track.quorum,
+			None => return false,
+		};
+		match quorum {
+			Quorum::Votes(votes) => {
+				let revealed = proposal_data
+					.ayes
+					.saturating_add(proposal_data.nays)
+					.saturating_add(proposal_data.abstains);
+				let options: u32 = proposal_data.tallies.iter().sum();
+				revealed.saturating_add(options) >= votes
+			},
+			Quorum::Members(share) =>
+				proposal_data.revealed.len() as u32 >= share.mul_ceil(proposal_data.members),
+		}
+	}
+
+	/// Whether the choices refer to distinct options of the proposal
+	/// and their quadratic cost matches the committed number
+	fn valid_choices(choices: &Choices, number: VoteToken, options: usize) -> bool {
+		let mut seen = [false; 256];
+		let mut cost: u32 = 0;
+		for (option, tokens) in choices.iter() {
+			if *option as usize >= options || seen[*option as usize] || *tokens == 0 {
+				return false
+			}
+			seen[*option as usize] = true;
+			cost = cost.saturating_add((*tokens as u32).saturating_pow(2));
+		}
+		cost == number as u32
+	}
+
+	/// The voters who revealed the given choice and the number of votes they gave
+	fn voters_of(proposal_data: &ProposalOf<T>, vote: &Vote) -> Vec<(T::AccountId, VoteToken)> {
+		proposal_data
+			.votes
+			.iter()
+			.filter(|entry| &entry.2 == vote)
+			.map(|entry| (entry.0.clone(), entry.1))
+			.collect()
+	}
+
+	/// Slashes the losing side and returns the total amount slashed and the part of it
+	/// the winners can be paid out of. The amount is decided by [`Config::SlashingPolicy`]
 	pub fn slash_voting_side(
-		voters: Vec<T::AccountId>,
-		pot: &T::AccountId,
-	) -> Result<BalanceOf<T>, DispatchError> {
+		voters: Vec<(T::AccountId, VoteToken)>,
+		margin: u32,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
 		let mut payout: BalanceOf<T> = BalanceOf::<T>::default();
-		for voter in voters {
-			let denominator: BalanceOf<T> = 10u8.into();
-			let slash = T::Currency::reserved_balance(&voter)
-				.checked_div(&denominator.clone())
-				.get_or_insert(BalanceOf::<T>::default())
-				.to_owned();
-			let lost = T::Currency::repatriate_reserved(
-				&voter,
-				pot,
-				slash,
-				frame_support::traits::BalanceStatus::Reserved,
-			)?;
-			//calculate how much funds have actually been slashed
-			let slashed = slash.saturating_sub(lost);
-			<Members<T>>::mutate(&voter, |balance| {
-				balance.reserved_balance = balance.reserved_balance.saturating_sub(slashed);
-			});
+		let mut credited: BalanceOf<T> = BalanceOf::<T>::default();
+		for (voter, weight) in voters {
+			let stake = Self::stake_of(&voter);
+			let slash = T::SlashingPolicy::slash(&Voter { who: voter.clone(), weight, stake }, margin)
+				.min(stake);
 			// even though we may not necessary
+			let (slashed, to_source) = Self::slash_stake(&voter, slash)?;
 			payout = payout.saturating_add(slashed);
+			credited = credited.saturating_add(to_source);
 		}
-		Ok(payout)
-	}
-
-	/// Rewards evenly every member from the pot with the provided sum
-	pub fn reward_voting_side(
-		voters: Vec<T::AccountId>,
-		pot: &T::AccountId,
-		total: BalanceOf<T>,
-	) -> Result<(), DispatchError> {
-		let len = voters.len() as u32;
-		let share = total / len.into();
-		for voter in voters {
-			let lost = T::Currency::repatriate_reserved(
-				pot,
-				&voter,
-				share,
-				frame_support::traits::BalanceStatus::Reserved,
-			)?;
-			let actual_share = share.saturating_sub(lost);
-			//increase the reserved funds under the account
-			<Members<T>>::mutate(&voter, |balance| {
-				balance.reserved_balance = balance.reserved_balance.saturating_add(actual_share);
-			});
+		Ok((payout, credited))
+	}
+
+	/// Slashes the amount from the stake of the member to [`Config::Slash`].
+	/// Returns the amount actually slashed and the part of it the winners can be paid out of
+	pub fn slash_stake(
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let (imbalance, _) = T::Currency::slash_reserved_named(&Self::reserve_id(), who, amount);
+		//calculate how much funds have actually been slashed
+		let slashed = imbalance.peek();
+		let credited = Self::handle_slash(imbalance);
+		<Members<T>>::mutate(who, |balance| {
+			balance.reserved_balance = balance.reserved_balance.saturating_sub(slashed);
+		});
+		Ok((slashed, credited))
+	}
+
+	/// Hands the slashed funds to [`Config::Slash`] and returns what the winners can be paid
+	/// for them: what reached [`Config::RewardSource`], or all of it if the source mints.
+	/// A minting source never pays out of the pot, so what lands there is burned instead
+	fn handle_slash(imbalance: NegativeImbalanceOf<T>) -> BalanceOf<T> {
+		let amount = imbalance.peek();
+		let pot = Self::account_id();
+		let in_pot = T::Currency::reserved_balance_named(&Self::reserve_id(), &pot);
+		let held = T::RewardSource::held();
+		T::Slash::on_unbalanced(imbalance);
+		match (held, T::RewardSource::held()) {
+			(Some(before), Some(after)) => after.saturating_sub(before),
+			_ => {
+				let landed = T::Currency::reserved_balance_named(&Self::reserve_id(), &pot)
+					.saturating_sub(in_pot);
+				let _ = T::Currency::slash_reserved_named(&Self::reserve_id(), &pot, landed);
+				amount
+			},
+		}
+	}
+
+	/// Pays the amount from [`Config::RewardSource`] to the stake of the member.
+	/// Returns the amount actually paid
+	pub fn reward_account(
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let actual_share = T::RewardSource::pay(who, amount)?;
+		//increase the reserved funds under the account
+		<Members<T>>::mutate(who, |balance| {
+			balance.reserved_balance = balance.reserved_balance.saturating_add(actual_share);
+		});
+		Ok(actual_share)
+	}
+
+	/// Handles funds of the pot that no proposal pays out as [`Config::Leftover`] says
+	fn release_leftover(amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
 		}
+		match T::Leftover::get() {
+			Leftover::RollOver => {
+				<CarryOver<T>>::mutate(|carried| *carried = carried.saturating_add(amount));
+				Self::deposit_event(Event::<T>::LeftoverCarried { amount });
+			},
+			Leftover::Treasury(to) => {
+				let imbalance = T::RewardSource::withdraw(amount);
+				let amount = imbalance.peek();
+				T::Currency::resolve_creating(&to, imbalance);
+				Self::deposit_event(Event::<T>::LeftoverTransferred { to, amount });
+			},
+			Leftover::Burn => {
+				let amount = T::RewardSource::withdraw(amount).peek();
+				Self::deposit_event(Event::<T>::LeftoverBurned { amount });
+			},
+		}
+		Ok(())
+	}
+
+	/// Checks that the funds held by [`Config::RewardSource`] are exactly those held for closed
+	/// proposals and carried over. A source that mints the rewards holds nothing to check
+	pub fn check_pot() -> Result<(), &'static str> {
+		let reserved = match T::RewardSource::held() {
+			Some(reserved) => reserved,
+			None => return Ok(()),
+		};
+		let held = <PotLedger<T>>::iter_values()
+			.fold(<CarryOver<T>>::get(), |total, held| total.saturating_add(held));
+		ensure!(reserved == held, "the reserved balance of the pot does not match the ledger");
 		Ok(())
 	}
 
@@ -671,3 +2389,22 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account_truncating()
 	}
 }
+
+/// Ensures that the origin is the committee dispatching an approved proposal.
+/// Yields the hash of the approved proposal.
+pub struct EnsureApproved<T>(PhantomData<T>);
+impl<O: Into<Result<RawOrigin<T::Hash>, O>> + From<RawOrigin<T::Hash>>, T: Config> EnsureOrigin<O>
+	for EnsureApproved<T>
+{
+	type Success = T::Hash;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|o| match o {
+			RawOrigin::Approved(hash) => hash,
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::Approved(Default::default()))
+	}
+}
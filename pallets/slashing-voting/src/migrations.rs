@@ -0,0 +1,285 @@
+//! Storage migrations of the pallet
+
+use super::*;
+use codec::Decode;
+use frame_support::{
+	traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	weights::Weight,
+};
+
+pub mod v1 {
+	use super::*;
+	use crate::types::{Data, Proposal, Vote};
+
+	/// The proposal format before tracks, deposits and archiving.
+	/// Closed proposals were kept with their payout
+	#[derive(Decode)]
+	pub struct OldProposal<AccountId, BlockNumber, Balance> {
+		pub title: Data,
+		pub proposer: AccountId,
+		pub ayes: u32,
+		pub nays: u32,
+		pub poll_end: BlockNumber,
+		pub reveal_end: Option<BlockNumber>,
+		pub votes: Vec<(AccountId, u8, Vote)>,
+		pub revealed: Vec<AccountId>,
+		pub payout: Balance,
+		pub closed: bool,
+	}
+
+	type OldProposalOf<T> = OldProposal<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	/// Moves the proposals to the current format.
+	///
+	/// Closed proposals are archived and free their slots, they were settled when closed.
+	/// Open proposals follow the first track of the runtime with a simple majority,
+	/// reserve no deposit and have the minimum length of the track as the unit of conviction locks.
+	/// Their commits are dropped by [`super::v2::MigrateToV2`], the revealed votes are kept
+	/// and the next phase transition is scheduled, at once if it is overdue.
+	/// The suspended members are counted, they are not waited for to commit.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(1) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let (track, track_info) = match T::Tracks::get().into_iter().next() {
+				Some((track, info)) => (track, Some(info)),
+				None => (Default::default(), None),
+			};
+			let length = track_info.map_or(Zero::zero(), |info| info.min_length);
+			//suspended members are not waited for
+			let suspended = <Suspensions<T>>::initialize_counter();
+			let electorate = <Members<T>>::count().saturating_sub(suspended);
+			let mut archived: u64 = 0;
+			let mut translated: u64 = 0;
+			let mut claims: u64 = 0;
+			<ProposalData<T>>::translate::<OldProposalOf<T>, _>(|hash, old| {
+				if old.closed {
+					let outcome = match old.ayes.cmp(&old.nays) {
+						Ordering::Greater => Outcome::Approved,
+						Ordering::Less => Outcome::Disapproved,
+						Ordering::Equal => Outcome::Tie,
+					};
+					let closed_at = old.reveal_end.unwrap_or(old.poll_end);
+					let record = ArchivedProposal {
+						proposer: old.proposer,
+						track,
+						outcome,
+						ayes: old.ayes,
+						nays: old.nays,
+						abstains: 0,
+						tallies: Vec::new(),
+						revealed: old.revealed.len() as u32,
+						payout: old.payout,
+						closed_at,
+						executed: None,
+					};
+					<Archive<T>>::insert(hash, closed_at, record);
+					<Proposals<T>>::mutate(|proposals| proposals.retain(|p| p != &hash));
+					archived += 1;
+					return None
+				}
+
+				//the revealed voters get their voting tokens back by claiming
+				for (who, _, _) in old.votes.iter() {
+					<PendingClaims<T>>::insert(who, hash, ());
+					claims += 1;
+				}
+				let transition = match old.reveal_end {
+					Some(reveal_end) => (reveal_end, Transition::CloseReveal(hash)),
+					None => (old.poll_end, Transition::CloseVote(hash)),
+				};
+				//every active proposal fits in the schedule
+				let _ = <Schedule<T>>::try_append(transition.0.max(now), transition.1);
+				<TrackProposals<T>>::mutate(track, |active| *active = active.saturating_add(1));
+				translated += 1;
+				Some(Proposal {
+					title: old.title,
+					proposer: old.proposer,
+					track,
+					deposit: Zero::zero(),
+					ayes: old.ayes,
+					nays: old.nays,
+					abstains: 0,
+					threshold: Threshold::SimpleMajority,
+					options: Default::default(),
+					tallies: Vec::new(),
+					poll_end: old.poll_end,
+					length,
+					reveal_end: old.reveal_end,
+					voters: old.votes.len() as u32,
+					votes: old.votes,
+					convictions: Vec::new(),
+					revealed: old.revealed,
+					committed: Vec::new(),
+					electorate,
+					members: <Members<T>>::count(),
+					last_eligible: <JoinCount<T>>::get(),
+					turnout: 0,
+					unrevealed: 0,
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			// every proposal is read and rewritten, archived proposals also free their slot,
+			// open proposals are scheduled, counted in their track and claimed on by their voters,
+			// the suspended members are counted
+			let reads = archived * 2 + translated * 4 + suspended as u64 + 3;
+			let writes = archived * 3 + translated * 4 + claims + 2;
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The commit format before hash commitments.
+	/// The vote was signed together with a public salt
+	#[derive(Decode)]
+	pub struct OldCommit<Signature> {
+		pub signature: Signature,
+		pub number: u8,
+		pub salt: u32,
+	}
+
+	/// Replaces signature commits with hash commitments.
+	///
+	/// A signed vote can not be turned into a hash commitment, so every pending commit is dropped
+	/// and its voting tokens are refunded without slashing. The voters have to commit again
+	/// while the commit phase of the proposal is still open.
+	///
+	/// `Signature` is the signature type the pallet was configured with before the upgrade.
+	pub struct MigrateToV2<T, Signature>(PhantomData<(T, Signature)>);
+	impl<T: Config, Signature: Decode> OnRuntimeUpgrade for MigrateToV2<T, Signature> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(2) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut dropped: u64 = 0;
+			<Commits<T>>::translate::<OldCommit<Signature>, _>(|who, _proposal, old| {
+				Pallet::<T>::deposit_votes(&who, old.number.saturating_pow(2));
+				dropped += 1;
+				None
+			});
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			// every commit is read, removed and refunded to the member
+			T::DbWeight::get().reads_writes(dropped * 2 + 1, dropped * 2 + 1)
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Moves the committee stake and the pot under the named reserve of the pallet.
+	///
+	/// Stakes used to be held with anonymous reserves shared with other pallets,
+	/// so only the amount recorded in [`Members`] is moved for each member.
+	/// The record is updated with the amount that was actually moved.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(3) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let id = Pallet::<T>::reserve_id();
+			let mut moved: u64 = 0;
+			let members: Vec<T::AccountId> = <Members<T>>::iter_keys().collect();
+			for who in members {
+				let recorded = <Members<T>>::get(&who).reserved_balance;
+				let leftover = T::Currency::unreserve(&who, recorded);
+				let mut held = recorded.saturating_sub(leftover);
+				if T::Currency::reserve_named(&id, &who, held).is_err() {
+					// the funds stay free and the member can only leave
+					held = Zero::zero();
+				}
+				Pallet::<T>::set_reserved_balance(&who, held);
+				moved += 1;
+			}
+
+			let pot = Pallet::<T>::account_id();
+			let pot_balance = T::Currency::reserved_balance(&pot);
+			let leftover = T::Currency::unreserve(&pot, pot_balance);
+			let _ = T::Currency::reserve_named(&id, &pot, pot_balance.saturating_sub(leftover));
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			// every member record and both balances of the account are updated
+			T::DbWeight::get().reads_writes(moved * 3 + 3, moved * 3 + 3)
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+	use crate::types::{Commit, Conviction};
+
+	/// The commit format before conviction votes
+	#[derive(Decode)]
+	pub struct OldCommit<Hash> {
+		pub commitment: Hash,
+		pub number: u8,
+	}
+
+	/// Adds the conviction to pending commits.
+	///
+	/// Commits made before the upgrade count without conviction and lock nothing.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(4) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated: u64 = 0;
+			<Commits<T>>::translate::<OldCommit<T::Hash>, _>(|_who, _proposal, old| {
+				translated += 1;
+				Some(Commit {
+					commitment: old.commitment,
+					number: old.number,
+					conviction: Conviction::None,
+				})
+			});
+			StorageVersion::new(4).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Starts the ledger of the pot.
+	///
+	/// The funds reserved by the pot before the upgrade belong to no proposal,
+	/// so they are carried over to the reward of the next proposal with winners.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(5) {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pot = Pallet::<T>::account_id();
+			let reserved = T::Currency::reserved_balance_named(&Pallet::<T>::reserve_id(), &pot);
+			let held = <PotLedger<T>>::iter_values()
+				.fold(BalanceOf::<T>::zero(), |total, held| total.saturating_add(held));
+			<CarryOver<T>>::put(reserved.saturating_sub(held));
+			StorageVersion::new(5).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+	}
+}
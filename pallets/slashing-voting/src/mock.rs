@@ -1,18 +1,23 @@
 use crate as pallet_voting;
 use crate::types::*;
+use crate::rewards::{Mint, Pot, RewardSource};
+use crate::NegativeImbalanceOf;
 use frame_system::EnsureRoot;
 use frame_support::pallet_prelude::ConstU32;
 use frame_support::traits::ConstU128;
+use frame_support::traits::{EitherOfDiverse, OnUnbalanced};
 use frame_support::traits::{ConstU16, ConstU64};
 use frame_system as system;
 use frame_support::parameter_types;
+use frame_support::weights::Weight;
 use frame_support::PalletId;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup, IdentifyAccount, Verify},
-	MultiSignature,
+	MultiSignature, Perbill,
 };
 use frame_support::pallet_prelude::*;
+use pallet_identity::Judgement;
 use sp_core::{sr25519, Pair, Public};
 
 pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -39,7 +44,7 @@ pub type Index = u32;
 pub type Hash = sp_core::H256;
 
 /// should be random, but we leave it const for simplicity
-const SALT: u32 = 10u32;
+const SALT: [u8; 32] = [10u8; 32];
 
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -48,7 +53,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		QuadraticVoting: pallet_voting::{Pallet, Call, Storage, Event<T>},
+		QuadraticVoting: pallet_voting::{Pallet, Call, Storage, Event<T>, Origin},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
 	}
@@ -108,7 +113,7 @@ impl pallet_identity::Config for Test {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -123,38 +128,161 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 	pub const EntryFee: Balance = 30_000 * UNIT;
 	pub const MaxProposals: u32 = 10u32;
-	pub const RevealLength: BlockNumber = 50u64;
-	pub const MinLength: BlockNumber = 100u64;
 	pub const MaxTokens: u8 = 100u8;
+	pub const MaxDelegators: u32 = 10;
+	pub static MaxVoters: u32 = 200;
 	pub const VotingPalletId: PalletId = PalletId(*b"p/v8t1ng");
+	pub const MaxProposalWeight: Weight = 1_000_000_000;
+	pub static MaxTransitionWeight: Weight = Weight::MAX;
+	pub const ArchiveRetention: BlockNumber = 100u64;
+	pub const NonRevealPenalty: Perbill = Perbill::from_percent(20);
+	pub static RefundNonRevealed: bool = true;
+	pub static LeftoverRule: Leftover<AccountId> = Leftover::RollOver;
+	pub static KeeperBounty: Balance = 0;
+	pub static SlashToPot: bool = true;
+	pub static MintRewards: bool = false;
+	pub static MinJudgement: IdentityLevel = IdentityLevel::Unverified;
+	pub static Tracks: Vec<(TrackId, TrackInfo<Balance, BlockNumber>)> =
+		vec![(0, general_track())];
+}
+
+/// The track every test proposal follows unless the test sets up its own
+pub fn general_track() -> TrackInfo<Balance, BlockNumber> {
+	TrackInfo {
+		min_length: 100,
+		max_length: 1_000,
+		reveal_length: 50,
+		deposit: 0,
+		quorum: Quorum::Votes(0),
+		thresholds: vec![Threshold::SimpleMajority, Threshold::SuperMajority, Threshold::Unanimity],
+		max_proposals: 10,
+	}
 }
 
 pub struct VotingIdentityProvider;
 impl pallet_voting::IdentityProvider<AccountId> for VotingIdentityProvider {
-	fn check_existence(account: &AccountId) -> bool {
-		Identity::identity(account).is_some()
+	fn judgement(account: &AccountId) -> IdentityLevel {
+		let registration = match Identity::identity(account) {
+			Some(registration) => registration,
+			None => return IdentityLevel::None,
+		};
+		let judgements = registration.judgements;
+		if judgements.iter().any(|(_, judgement)| *judgement == Judgement::Erroneous) {
+			return IdentityLevel::None
+		}
+		judgements
+			.iter()
+			.map(|(_, judgement)| match judgement {
+				Judgement::KnownGood => IdentityLevel::KnownGood,
+				Judgement::Reasonable => IdentityLevel::Reasonable,
+				_ => IdentityLevel::Unverified,
+			})
+			.max()
+			.unwrap_or(IdentityLevel::Unverified)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_identity(account: &AccountId) {
+		let info = pallet_identity::IdentityInfo {
+			display: pallet_identity::Data::Raw(b"ten".to_vec().try_into().unwrap()),
+			additional: BoundedVec::default(),
+			legal: Default::default(),
+			web: Default::default(),
+			riot: Default::default(),
+			twitter: Default::default(),
+			email: Default::default(),
+			pgp_fingerprint: Default::default(),
+			image: Default::default(),
+		};
+		let _ = Identity::set_identity(Origin::signed(account.clone()), Box::new(info));
+	}
+}
+
+/// Root or a proposal approved by the committee
+type RootOrCommittee = EitherOfDiverse<EnsureRoot<AccountId>, pallet_voting::EnsureApproved<Test>>;
+
+/// The pot, or burning if [`SlashToPot`] is off
+pub struct MockSlash;
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for MockSlash {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+		if SlashToPot::get() {
+			Pot::<Test>::on_unbalanced(amount);
+		}
+	}
+}
+
+/// The pot, or minting if [`MintRewards`] is on
+pub struct MockRewards;
+impl RewardSource<AccountId, Balance, NegativeImbalanceOf<Test>> for MockRewards {
+	fn pay(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		if MintRewards::get() {
+			Mint::<Test>::pay(who, amount)
+		} else {
+			Pot::<Test>::pay(who, amount)
+		}
+	}
+
+	fn withdraw(amount: Balance) -> NegativeImbalanceOf<Test> {
+		if MintRewards::get() {
+			Mint::<Test>::withdraw(amount)
+		} else {
+			Pot::<Test>::withdraw(amount)
+		}
+	}
+
+	fn held() -> Option<Balance> {
+		if MintRewards::get() {
+			Mint::<Test>::held()
+		} else {
+			Pot::<Test>::held()
+		}
+	}
+
+	fn endow() {
+		Pot::<Test>::endow()
 	}
 }
 
 impl pallet_voting::Config for Test {
 	type Event = Event;
 	type IdentityProvider = VotingIdentityProvider;
+	type MinJudgement = MinJudgement;
 	type Currency = Balances;
 	type BasicDeposit = EntryFee;
 	type MaxProposals = MaxProposals;
-	type Public = <Signature as Verify>::Signer;
-	type Signature = MultiSignature;
-	type RevealLength = RevealLength;
-	type MinLength = MinLength;
+	type Tracks = Tracks;
 	type MaxVotingTokens = MaxTokens;
+	type MaxDelegators = MaxDelegators;
+	type MaxVotersPerProposal = MaxVoters;
 	type PalletId = VotingPalletId;
+	type Origin = Origin;
+	type Proposal = Call;
+	type CancelOrigin = RootOrCommittee;
+	type MembershipOrigin = RootOrCommittee;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxTransitionWeight = MaxTransitionWeight;
+	type ArchiveRetention = ArchiveRetention;
+	type NonRevealPenalty = NonRevealPenalty;
+	type RefundNonRevealed = RefundNonRevealed;
+	type SlashingPolicy = pallet_voting::policy::DefaultPolicy;
+	type Leftover = LeftoverRule;
+	type Slash = MockSlash;
+	type RewardSource = MockRewards;
+	type KeeperBounty = KeeperBounty;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(get_alice(), 1_000_000 * UNIT), (get_bob(), 1_000_000 * UNIT), (get_charlie(), 20_000 * UNIT)]
+		balances: vec![
+			(get_alice(), 1_000_000 * UNIT),
+			(get_bob(), 1_000_000 * UNIT),
+			(get_charlie(), 20_000 * UNIT),
+			// the pot must exist to receive slashed stakes
+			(QuadraticVoting::account_id(), 500),
+		],
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -174,12 +302,18 @@ pub fn get_charlie() -> AccountId {
 	get_account_id_from_seed::<sr25519::Public>("Charlie")
 }
 
-pub fn generate(account: &str, vote: Vote) -> (sp_core::sr25519::Signature, u32) {
-	let pair: sp_core::sr25519::Pair = Pair::from_string(account, None).unwrap();
-	let payload = (vote, SALT).encode();
-	let payload = payload.as_slice().to_owned();
-	let signed = pair.sign(&payload);
-	(signed, SALT)
+/// Moves the chain to the given block, applying the hooks of the pallet
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		<QuadraticVoting as Hooks<BlockNumber>>::on_initialize(next);
+	}
+}
+
+pub fn generate(who: &AccountId, proposal: Hash, vote: Vote, number: u8) -> (Hash, [u8; 32]) {
+	let commitment = QuadraticVoting::commitment_of(who, &proposal, &vote, number, &SALT);
+	(commitment, SALT)
 }
 
 /// Generate a crypto pair from seed.
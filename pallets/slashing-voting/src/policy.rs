@@ -0,0 +1,130 @@
+//! Policies deciding how voters are slashed and rewarded
+
+use crate::types::VoteToken;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One},
+	Perbill, RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+/// A voter as seen by the slashing policy
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Voter<AccountId, Balance> {
+	pub who: AccountId,
+	/// The number of votes given
+	pub weight: VoteToken,
+	/// The committee stake of the voter
+	pub stake: Balance,
+}
+
+/// Decides how much is slashed from voters and how the pot is paid out
+pub trait SlashingPolicy<AccountId, Balance> {
+	/// The amount slashed from a voter on the losing side.
+	/// `margin` is the difference between the tallies of both sides
+	fn slash(voter: &Voter<AccountId, Balance>, margin: u32) -> Balance;
+
+	/// The shares of a winner in the pot, each winner is paid out pro rata to its shares.
+	/// If no winner has any shares, the pot is split equally
+	fn share(winner: &Voter<AccountId, Balance>) -> Balance;
+
+	/// Whether every voter is slashed in favour of the proposer on a tie
+	fn slash_on_tie() -> bool;
+}
+
+/// How the pot is split among the winners
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum Split {
+	/// Every winner receives the same share
+	Equal,
+	/// Pro rata by the number of votes given
+	ByWeight,
+	/// Pro rata by the committee stake
+	ByStake,
+}
+
+/// What the slash of a voter on the losing side grows with
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum SlashScale {
+	/// The same share of the stake whatever the vote
+	Flat,
+	/// The share of the stake for every vote given, up to the whole stake
+	ByWeight,
+	/// The share of the stake for every vote the losing side lost by, up to the whole stake
+	ByMargin,
+}
+
+/// Scales the slash of [`ConfigurablePolicy`] by nothing, the default
+pub struct FlatSlash;
+impl Get<SlashScale> for FlatSlash {
+	fn get() -> SlashScale {
+		SlashScale::Flat
+	}
+}
+
+/// Slashes 10% of the stake whatever the vote and the margin, splits the pot equally
+/// and slashes everyone in favour of the proposer on a tie
+pub struct DefaultPolicy;
+impl<AccountId: Clone, Balance: AtLeast32BitUnsigned + Copy> SlashingPolicy<AccountId, Balance>
+	for DefaultPolicy
+{
+	fn slash(voter: &Voter<AccountId, Balance>, _margin: u32) -> Balance {
+		voter.stake / 10u8.into()
+	}
+
+	fn share(winner: &Voter<AccountId, Balance>) -> Balance {
+		share(winner, Split::Equal)
+	}
+
+	fn slash_on_tie() -> bool {
+		true
+	}
+}
+
+/// Slashes `Ratio` of the stake scaled according to `Scale`
+/// and splits the pot according to `Mode`
+pub struct ConfigurablePolicy<Ratio, Mode, SlashOnTie, Scale = FlatSlash>(
+	PhantomData<(Ratio, Mode, SlashOnTie, Scale)>,
+);
+impl<AccountId, Balance, Ratio, Mode, SlashOnTie, Scale> SlashingPolicy<AccountId, Balance>
+	for ConfigurablePolicy<Ratio, Mode, SlashOnTie, Scale>
+where
+	AccountId: Clone,
+	Balance: AtLeast32BitUnsigned + Copy,
+	Ratio: Get<Perbill>,
+	Mode: Get<Split>,
+	SlashOnTie: Get<bool>,
+	Scale: Get<SlashScale>,
+{
+	fn slash(voter: &Voter<AccountId, Balance>, margin: u32) -> Balance {
+		let slash = Ratio::get() * voter.stake;
+		let factor: Balance = match Scale::get() {
+			SlashScale::Flat => return slash,
+			SlashScale::ByWeight => voter.weight.into(),
+			SlashScale::ByMargin => margin.into(),
+		};
+		slash.saturating_mul(factor).min(voter.stake)
+	}
+
+	fn share(winner: &Voter<AccountId, Balance>) -> Balance {
+		share(winner, Mode::get())
+	}
+
+	fn slash_on_tie() -> bool {
+		SlashOnTie::get()
+	}
+}
+
+/// The shares of a winner according to `mode`
+pub fn share<AccountId, Balance: AtLeast32BitUnsigned + Copy>(
+	winner: &Voter<AccountId, Balance>,
+	mode: Split,
+) -> Balance {
+	match mode {
+		Split::Equal => One::one(),
+		Split::ByWeight => winner.weight.into(),
+		Split::ByStake => winner.stake,
+	}
+}
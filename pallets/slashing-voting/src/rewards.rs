@@ -0,0 +1,91 @@
+//! Where the slashed funds go and where the rewards come from
+
+use crate::{BalanceOf, Config, NegativeImbalanceOf, Pallet};
+use frame_support::traits::{
+	BalanceStatus, Currency, Imbalance, NamedReservableCurrency, OnUnbalanced,
+};
+use sp_runtime::{traits::Saturating, DispatchError};
+use sp_std::marker::PhantomData;
+
+/// Pays the rewards of the winners
+pub trait RewardSource<AccountId, Balance, NegativeImbalance> {
+	/// Pays the amount into the stake of the member.
+	/// Returns the amount actually paid
+	fn pay(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError>;
+
+	/// Takes up to the amount out of the funds held for rewards
+	fn withdraw(amount: Balance) -> NegativeImbalance;
+
+	/// The funds held for rewards, `None` if the source is not limited
+	fn held() -> Option<Balance>;
+
+	/// Sets the source up at genesis
+	fn endow();
+}
+
+/// The pot of the pallet, reserved by [`Pallet::account_id`].
+/// Receives the slashed funds as [`Config::Slash`] and pays the rewards out of them
+pub struct Pot<T>(PhantomData<T>);
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pot<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let pot = Pallet::<T>::account_id();
+		let value = amount.peek();
+		T::Currency::resolve_creating(&pot, amount);
+		let _ = T::Currency::reserve_named(&Pallet::<T>::reserve_id(), &pot, value);
+	}
+}
+
+impl<T: Config> RewardSource<T::AccountId, BalanceOf<T>, NegativeImbalanceOf<T>> for Pot<T> {
+	fn pay(who: &T::AccountId, amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let lost = T::Currency::repatriate_reserved_named(
+			&Pallet::<T>::reserve_id(),
+			&Pallet::<T>::account_id(),
+			who,
+			amount,
+			BalanceStatus::Reserved,
+		)?;
+		Ok(amount.saturating_sub(lost))
+	}
+
+	fn withdraw(amount: BalanceOf<T>) -> NegativeImbalanceOf<T> {
+		let pot = Pallet::<T>::account_id();
+		T::Currency::slash_reserved_named(&Pallet::<T>::reserve_id(), &pot, amount).0
+	}
+
+	fn held() -> Option<BalanceOf<T>> {
+		let pot = Pallet::<T>::account_id();
+		Some(T::Currency::reserved_balance_named(&Pallet::<T>::reserve_id(), &pot))
+	}
+
+	fn endow() {
+		//the pot must exist to receive slashed stakes
+		let pot = Pallet::<T>::account_id();
+		let min = T::Currency::minimum_balance();
+		if T::Currency::free_balance(&pot) < min {
+			let _ = T::Currency::make_free_balance_be(&pot, min);
+		}
+	}
+}
+
+/// Mints the rewards, i.e. pays them out of inflation.
+/// Holds nothing, so leftovers have nothing to move or burn and slashes left in the pot are burned
+pub struct Mint<T>(PhantomData<T>);
+
+impl<T: Config> RewardSource<T::AccountId, BalanceOf<T>, NegativeImbalanceOf<T>> for Mint<T> {
+	fn pay(who: &T::AccountId, amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let paid = T::Currency::deposit_creating(who, amount).peek();
+		T::Currency::reserve_named(&Pallet::<T>::reserve_id(), who, paid)?;
+		Ok(paid)
+	}
+
+	fn withdraw(_amount: BalanceOf<T>) -> NegativeImbalanceOf<T> {
+		NegativeImbalanceOf::<T>::zero()
+	}
+
+	fn held() -> Option<BalanceOf<T>> {
+		None
+	}
+
+	fn endow() {}
+}
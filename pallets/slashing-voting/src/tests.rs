@@ -1,10 +1,17 @@
 use crate::mock::Identity;
+use crate::mock::{Call, Event, Origin};
+use crate::rewards::{self, RewardSource};
 use crate::types::*;
 use crate::*;
 use crate::{mock::*, Error};
 use frame_support::pallet_prelude::*;
-use frame_support::{assert_noop, assert_ok};
-use pallet_identity::IdentityInfo;
+use frame_support::traits::{
+	ConstBool, Currency, Imbalance, NamedReservableCurrency, OnRuntimeUpgrade, OnUnbalanced,
+	ReservableCurrency,
+};
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use pallet_identity::{IdentityInfo, Judgement};
+use sp_runtime::Perbill;
 
 #[test]
 fn not_join_without_identity() {
@@ -24,6 +31,8 @@ fn disallow_action_for_non_members() {
 		let result = QuadraticVoting::create_proposal(
 			bob_origin,
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 		assert_noop!(result, Error::<Test>::NotMember);
@@ -53,6 +62,8 @@ fn create_proposal_success() {
 		let result = QuadraticVoting::create_proposal(
 			origin,
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 		assert_ok!(result);
@@ -74,11 +85,15 @@ fn no_proposal_duplicates() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 		let result = QuadraticVoting::create_proposal(
 			origin,
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 		assert_noop!(result, Error::<Test>::DuplicateProposal);
@@ -97,15 +112,22 @@ fn submit_commits() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let result = QuadraticVoting::commit_vote(origin, proposal_hash, sig, 8, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let result = QuadraticVoting::commit_vote(
+			origin,
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 		assert_ok!(result);
 	});
 }
@@ -122,15 +144,22 @@ fn cannot_submit_votes_more_than_have() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let result = QuadraticVoting::commit_vote(origin, proposal_hash, sig, 11, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 11);
+		let result = QuadraticVoting::commit_vote(
+			origin,
+			proposal_hash,
+			commitment,
+			11,
+			Conviction::None,
+		);
 		assert_noop!(result, Error::<Test>::NotEnoughVotingTokens);
 	});
 }
@@ -147,17 +176,24 @@ fn cannot_commit_after_deadline() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		System::set_block_number(System::block_number().saturating_add(105));
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let result = QuadraticVoting::commit_vote(origin, proposal_hash, sig, 5, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 5);
+		let result = QuadraticVoting::commit_vote(
+			origin,
+			proposal_hash,
+			commitment,
+			5,
+			Conviction::None,
+		);
 		assert_noop!(result, Error::<Test>::VoteEnded);
 	});
 }
@@ -174,19 +210,26 @@ fn reveal_vote_success() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		System::set_block_number(System::block_number().saturating_add(20));
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin.clone(), proposal_hash, sig, 8, salt);
+		let (commitment, salt) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 
-		let result = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes);
+		let result = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, salt);
 		assert_ok!(result);
 	});
 }
@@ -203,20 +246,170 @@ fn cannot_reveal_incorrect_vote() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		System::set_block_number(System::block_number().saturating_add(20));
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin.clone(), proposal_hash, sig, 8, salt);
+		let (commitment, salt) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		let result = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::No, salt);
+		assert_noop!(result, Error::<Test>::CommitmentMismatch);
+	});
+}
+
+#[test]
+fn cannot_reveal_with_wrong_salt() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		let result = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, [0u8; 32]);
+		assert_noop!(result, Error::<Test>::CommitmentMismatch);
+	});
+}
+
+#[test]
+fn cannot_replay_commit_on_another_proposal() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(b"second".to_vec().try_into().unwrap())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+
+		let proposals = <Proposals<Test>>::get();
+		let (first, second) = (proposals[0], proposals[1]);
+
+		// the commitment made for the first proposal is submitted to both of them
+		let (commitment, salt) = generate(&alice, first, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin.clone(),
+			first,
+			commitment,
+			3,
+			Conviction::None,
+		));
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin.clone(),
+			second,
+			commitment,
+			3,
+			Conviction::None,
+		));
+
+		assert_ok!(QuadraticVoting::reveal_vote(origin.clone(), first, Vote::Yes, salt));
+		let result = QuadraticVoting::reveal_vote(origin, second, Vote::Yes, salt);
+		assert_noop!(result, Error::<Test>::CommitmentMismatch);
+	});
+}
+
+#[test]
+fn cannot_replay_commit_of_another_voter() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let origin_bob = Origin::signed(get_bob());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		// bob copies the commitment of alice and tries to reveal it
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice,
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+
+		let result = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::Yes, salt);
+		assert_noop!(result, Error::<Test>::CommitmentMismatch);
+	});
+}
+
+#[test]
+fn commitment_bound_to_chain_and_weight() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let proposal_hash = sp_core::H256::repeat_byte(1);
 
-		let result = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::No);
-		assert_noop!(result, Error::<Test>::SignatureInvalid);
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let (other_weight, _) = generate(&alice, proposal_hash, Vote::Yes, 4);
+		assert_ne!(commitment, other_weight);
+
+		// the same vote on a chain with a different genesis
+		<frame_system::BlockHash<Test>>::insert(0, sp_core::H256::repeat_byte(2));
+		let (other_chain, _) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		assert_ne!(commitment, other_chain);
 	});
 }
 
@@ -232,6 +425,8 @@ fn close_vote_success() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
@@ -254,6 +449,8 @@ fn cannot_close_vote_before_deadline() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
@@ -275,6 +472,8 @@ fn close_reveal_success() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
@@ -302,6 +501,8 @@ fn cannot_close_reveal_early() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
@@ -329,6 +530,8 @@ fn cannot_close_reveal_before_vote_end() {
 		let _ = QuadraticVoting::create_proposal(
 			origin.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
@@ -341,6 +544,191 @@ fn cannot_close_reveal_before_vote_end() {
 	});
 }
 
+#[test]
+fn phases_closed_automatically() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		assert_eq!(<Schedule<Test>>::get(101).into_inner(), vec![Transition::CloseVote(proposal_hash)]);
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		run_to_block(100);
+		assert!(<ProposalData<Test>>::get(proposal_hash).unwrap().reveal_end.is_none());
+
+		run_to_block(101);
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!(proposal.reveal_end, Some(151));
+		assert!(!<Schedule<Test>>::contains_key(101));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::ClosedCommit(proposal_hash)));
+
+		assert_ok!(QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::Yes, salt));
+
+		run_to_block(151);
+		assert!(<Archive<Test>>::contains_key(proposal_hash, 151));
+		assert!(!<Schedule<Test>>::contains_key(151));
+
+		assert_ok!(QuadraticVoting::claim(origin, proposal_hash, alice.clone()));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn manual_close_unschedules_transition() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		// the hook has not been run at block 100
+		System::set_block_number(110);
+		assert_ok!(QuadraticVoting::close_vote(origin.clone(), proposal_hash));
+		assert!(!<Schedule<Test>>::contains_key(100));
+		assert!(<Schedule<Test>>::contains_key(160));
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin, proposal_hash));
+		assert!(!<Schedule<Test>>::contains_key(160));
+	});
+}
+
+#[test]
+fn busy_block_defers_transitions() {
+	new_test_ext().execute_with(|| {
+		//only the first transition of a block fits
+		MaxTransitionWeight::set(1);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		for text in 0..3 {
+			assert_ok!(QuadraticVoting::create_proposal(
+				origin.clone(),
+				Box::new(Data::Raw(vec![text].try_into().unwrap())),
+				0,
+				Threshold::SimpleMajority,
+				100,
+			));
+		}
+		let proposals = <Proposals<Test>>::get();
+		let reveal_end = |index: usize| {
+			<ProposalData<Test>>::get(proposals[index]).unwrap().reveal_end
+		};
+
+		run_to_block(100);
+		assert_eq!(reveal_end(0), Some(150));
+		assert_eq!(reveal_end(1), None);
+		let deferred =
+			vec![Transition::CloseVote(proposals[1]), Transition::CloseVote(proposals[2])];
+		assert_eq!(<Schedule<Test>>::get(101).into_inner(), deferred);
+
+		run_to_block(101);
+		assert_eq!(reveal_end(1), Some(151));
+		assert_eq!(reveal_end(2), None);
+		assert_eq!(
+			<Schedule<Test>>::get(102).into_inner(),
+			vec![Transition::CloseVote(proposals[2])]
+		);
+
+		//a deferred transition is unscheduled when the phase is closed manually
+		assert_ok!(QuadraticVoting::close_vote(origin, proposals[2]));
+		assert!(!<Schedule<Test>>::contains_key(102));
+		assert_eq!(reveal_end(2), Some(151));
+	});
+}
+
+#[test]
+fn failed_transition_reported() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//the end of the reveal phase cannot be scheduled
+		let other = Transition::CloseReveal(sp_core::H256::repeat_byte(9));
+		let full = vec![other; MaxProposals::get() as usize];
+		<Schedule<Test>>::insert(150, BoundedVec::try_from(full).unwrap());
+		System::set_block_number(100);
+		let weight = <QuadraticVoting as Hooks<BlockNumber>>::on_initialize(100);
+
+		let voters = MaxVoters::get();
+		assert!(weight >= <() as WeightInfo>::close_vote(voters));
+		assert!(<ProposalData<Test>>::get(proposal_hash).unwrap().reveal_end.is_none());
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::TransitionFailed {
+			transition: Transition::CloseVote(proposal_hash),
+			error: Error::<Test>::TooManyProposals.into(),
+		}));
+	});
+}
+
+#[test]
+fn removed_track_does_not_hold_proposal_open() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//the reveal phase lasts as long as the commit phase did
+		Tracks::set(Vec::new());
+		run_to_block(100);
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().reveal_end, Some(200));
+		run_to_block(200);
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 200).unwrap().outcome, Outcome::NoQuorum);
+	});
+}
+
 #[test]
 fn slashed_correctly() {
 	new_test_ext().execute_with(|| {
@@ -357,22 +745,34 @@ fn slashed_correctly() {
 		let _ = QuadraticVoting::create_proposal(
 			origin_alice.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_alice, proposal_hash, sig, 8, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice,
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 
-		let (sig, salt) = generate("//Bob", Vote::No);
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_bob.clone(), proposal_hash, sig, 2, salt);
+		let (commitment, _) = generate(&get_bob(), proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
 
 		System::set_block_number(101);
 
@@ -384,13 +784,16 @@ fn slashed_correctly() {
 		let alice_original_balance = <Members<Test>>::get(alice.clone()).reserved_balance;
 		let bob_original_balance = <Members<Test>>::get(bob.clone()).reserved_balance;
 
-		let _ = QuadraticVoting::close_reveal(origin_bob, proposal_hash);
+		let _ = QuadraticVoting::close_reveal(origin_bob.clone(), proposal_hash);
+		let _ = QuadraticVoting::claim(origin_bob.clone(), proposal_hash, bob.clone());
+		let _ = QuadraticVoting::claim(origin_bob, proposal_hash, alice.clone());
 
 		let alice_current_balance = <Members<Test>>::get(alice).reserved_balance;
 		let bob_current_balance = <Members<Test>>::get(bob).reserved_balance;
 
 		let slash = bob_original_balance - bob_current_balance;
 		assert!(alice_current_balance == alice_original_balance + slash);
+		assert_ok!(QuadraticVoting::check_pot());
 	});
 }
 
@@ -410,22 +813,34 @@ fn votes_deducted_and_refunded() {
 		let _ = QuadraticVoting::create_proposal(
 			origin_alice.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_alice.clone(), proposal_hash, sig, 8, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 
 		let alice_original_votes = <Members<Test>>::get(alice.clone()).voting_tokens;
 		assert!(alice_original_votes == MaxTokens::get() - 8_u8.pow(2));
 
-		let (sig, salt) = generate("//Bob", Vote::No);
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_bob.clone(), proposal_hash, sig, 2, salt);
+		let (commitment, salt) = generate(&get_bob(), proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
 
 		let bob_original_votes = <Members<Test>>::get(bob.clone()).voting_tokens;
 		assert!(bob_original_votes == MaxTokens::get() - 2_u8.pow(2));
@@ -435,12 +850,14 @@ fn votes_deducted_and_refunded() {
 		let proposal_hash = <Proposals<Test>>::get()[0];
 		let _ = QuadraticVoting::close_vote(origin_bob.clone(), proposal_hash);
 
-		let _ = QuadraticVoting::reveal_vote(origin_alice, proposal_hash, Vote::Yes);
-		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No);
+		let _ = QuadraticVoting::reveal_vote(origin_alice, proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No, salt);
 
 		System::set_block_number(160);
 
-		let _ = QuadraticVoting::close_reveal(origin_bob, proposal_hash);
+		let _ = QuadraticVoting::close_reveal(origin_bob.clone(), proposal_hash);
+		assert_ok!(QuadraticVoting::claim(origin_bob.clone(), proposal_hash, bob.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_bob, proposal_hash, alice.clone()));
 
 		let alice_tokens = <Members<Test>>::get(alice).voting_tokens;
 		let bob_tokens = <Members<Test>>::get(bob).voting_tokens;
@@ -461,15 +878,22 @@ fn cannot_leave_while_in_vote() {
 		let _ = QuadraticVoting::create_proposal(
 			origin_alice.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_alice.clone(), proposal_hash, sig, 8, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 
 		let result = QuadraticVoting::leave_committee(origin_alice.clone());
 		assert_noop!(result, Error::<Test>::InMotion);
@@ -492,40 +916,58 @@ fn cashout() {
 		let _ = QuadraticVoting::join_committee(origin_alice.clone());
 
 		let bob = get_bob();
-		let origin_bob = Origin::signed(bob);
+		let origin_bob = Origin::signed(bob.clone());
 		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
 		let _ = QuadraticVoting::join_committee(origin_bob.clone());
 
 		let _ = QuadraticVoting::create_proposal(
 			origin_alice.clone(),
 			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
 			100,
 		);
 
 		let results = <Proposals<Test>>::get();
 		let proposal_hash = results[0];
 
-		let (sig, salt) = generate("//Alice", Vote::Yes);
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_alice.clone(), proposal_hash, sig, 8, salt);
+		let (commitment, _) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
 
 
-		let (sig, salt) = generate("//Bob", Vote::No);
-		let sig = sp_runtime::MultiSignature::Sr25519(sig);
-		let _ = QuadraticVoting::commit_vote(origin_bob.clone(), proposal_hash, sig, 2, salt);
+		let (commitment, salt) = generate(&get_bob(), proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
 
 		System::set_block_number(101);
 
 		let proposal_hash = <Proposals<Test>>::get()[0];
 		let _ = QuadraticVoting::close_vote(origin_bob.clone(), proposal_hash);
 
-		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes);
-		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No, salt);
 
 		System::set_block_number(160);
 
-		let _ = QuadraticVoting::close_reveal(origin_bob, proposal_hash);
+		let _ = QuadraticVoting::close_reveal(origin_bob.clone(), proposal_hash);
 
+		//the reward of alice waits for the slash of bob
+		let result = QuadraticVoting::leave_committee(origin_alice.clone());
+		assert_noop!(result, Error::<Test>::InMotion);
+		assert_ok!(QuadraticVoting::claim(origin_bob, proposal_hash, bob));
+
+		//alice claims on the way out
 		let result = QuadraticVoting::leave_committee(origin_alice);
 		assert_ok!(result);
 
@@ -533,6 +975,3187 @@ fn cashout() {
 	});
 }
 
+#[test]
+fn non_revealers_slashed_on_claim() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		));
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		));
+
+		System::set_block_number(101);
+		assert_ok!(QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash));
+		assert_ok!(QuadraticVoting::reveal_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			Vote::Yes,
+			salt
+		));
+
+		System::set_block_number(151);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+		assert!(<Commits<Test>>::get(&bob, proposal_hash).is_some());
+
+		//alice gets the tokens back but is paid once the slash of bob is in
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		let result = QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone());
+		assert_noop!(result, Error::<Test>::SlashesPending);
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, bob.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, alice.clone()));
+
+		let slashed = NonRevealPenalty::get() * bob_balance;
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::NonRevealed {
+			who: bob.clone(),
+			slashed,
+		}));
+		assert_eq!(<Members<Test>>::get(&bob).reserved_balance, bob_balance - slashed);
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance + slashed);
+		assert_eq!(<Members<Test>>::get(&bob).voting_tokens, MaxTokens::get());
+		assert!(<Commits<Test>>::get(&bob, proposal_hash).is_none());
+
+		//nothing holds bob in the committee anymore
+		assert_ok!(QuadraticVoting::leave_committee(origin_bob));
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn non_revealed_tokens_burned_when_configured() {
+	new_test_ext().execute_with(|| {
+		RefundNonRevealed::set(false);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		));
+
+		run_to_block(152);
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, alice.clone()));
+
+		assert!(<Commits<Test>>::get(&alice, proposal_hash).is_none());
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 9);
+	});
+}
+
+#[test]
+fn only_committee_stake_slashed() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+		assert_eq!(
+			Balances::reserved_balance_named(&QuadraticVoting::reserve_id(), &bob),
+			EntryFee::get()
+		);
+
+		// funds reserved by another pallet
+		let other_deposit = 5_000 * UNIT;
+		assert_ok!(Balances::reserve(&bob, other_deposit));
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No, salt);
+
+		System::set_block_number(151);
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice, proposal_hash));
+		assert_ok!(QuadraticVoting::claim(origin_bob.clone(), proposal_hash, bob.clone()));
+
+		let stake = EntryFee::get() - EntryFee::get() / 10;
+		assert_eq!(<Members<Test>>::get(&bob).reserved_balance, stake);
+		assert_eq!(Balances::reserved_balance_named(&QuadraticVoting::reserve_id(), &bob), stake);
+
+		assert_ok!(QuadraticVoting::leave_committee(origin_bob));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Left {
+			account: bob.clone(),
+			cashout: stake,
+		}));
+		assert_eq!(Balances::reserved_balance(&bob), other_deposit);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn stake_moved_under_named_reserve_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin);
+
+		// the stake in the old format is held with an anonymous reserve
+		let id = QuadraticVoting::reserve_id();
+		let _ = Balances::unreserve_named(&id, &alice, EntryFee::get());
+		assert_ok!(Balances::reserve(&alice, EntryFee::get()));
+		StorageVersion::new(2).put::<QuadraticVoting>();
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Balances::reserved_balance_named(&id, &alice), EntryFee::get());
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get());
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), StorageVersion::new(3));
+	});
+}
+
+#[test]
+fn commits_without_conviction_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let proposal_hash = sp_core::H256::repeat_byte(1);
+		let commitment = sp_core::H256::repeat_byte(2);
+
+		// the commit in the old format, without a conviction
+		let key = <Commits<Test>>::hashed_key_for(&alice, &proposal_hash);
+		frame_support::storage::unhashed::put(&key, &(commitment, 4u8));
+		StorageVersion::new(3).put::<QuadraticVoting>();
+
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			<Commits<Test>>::get(&alice, &proposal_hash),
+			Some(Commit { commitment, number: 4, conviction: Conviction::None })
+		);
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), StorageVersion::new(4));
+	});
+}
+
+#[test]
+fn approved_proposal_dispatches_call() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let call = Call::System(frame_system::Call::set_heap_pages { pages: 10 });
+		let result = QuadraticVoting::create_call_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			Box::new(call),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		assert_ok!(result);
+
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		assert!(<ProposalCalls<Test>>::contains_key(proposal_hash));
+
+		let (commitment, salt) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::Yes, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin, proposal_hash));
+
+		// `set_heap_pages` requires root, the committee origin only passes the origins
+		// of the pallet
+		let result = Err(DispatchError::BadOrigin);
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.executed, Some(result));
+		assert!(!<ProposalCalls<Test>>::contains_key(proposal_hash));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Executed {
+			proposal_hash,
+			result,
+		}));
+	});
+}
+
+#[test]
+fn approved_proposal_adds_member() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		//the committee may add members through the membership origin
+		let dave = get_account_id_from_seed::<sp_core::sr25519::Public>("Dave");
+		let _ = Balances::set_balance(Origin::root(), dave.clone(), 1_000_000 * UNIT, 0);
+		let call = Call::QuadraticVoting(crate::Call::force_add_member { who: dave.clone() });
+		assert_ok!(QuadraticVoting::create_call_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			Box::new(call),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&get_alice(), proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::Yes, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin, proposal_hash));
+
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.executed, Some(Ok(())));
+		assert!(QuadraticVoting::is_member(&dave));
+		assert_eq!(Balances::reserved_balance(&dave), EntryFee::get());
+	});
+}
+
+#[test]
+fn approved_call_finds_proposal_closed() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		//the committee removes its only voter
+		let call = Call::QuadraticVoting(crate::Call::kick_member {
+			who: alice.clone(),
+			forfeit: Perbill::zero(),
+		});
+		assert_ok!(QuadraticVoting::create_call_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			Box::new(call),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 8);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			8,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::Yes, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin, proposal_hash));
+
+		//the vote was settled before the voter was removed
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.executed, Some(Ok(())));
+		assert!(!QuadraticVoting::is_member(&alice));
+		assert!(<Proposals<Test>>::get().is_empty());
+		assert!(!<Settlements<Test>>::contains_key(proposal_hash));
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn approved_origin_yields_proposal_hash() {
+	new_test_ext().execute_with(|| {
+		let hash = sp_core::H256::repeat_byte(1);
+		let origin: Origin = RawOrigin::Approved(hash).into();
+		assert_eq!(EnsureApproved::<Test>::try_origin(origin).ok(), Some(hash));
+		assert!(EnsureApproved::<Test>::try_origin(Origin::root()).is_err());
+	});
+}
+
+#[test]
+fn proposals_moved_to_current_format_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(120);
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		// proposals in the old format, one settled and one in its reveal phase
+		let open = sp_core::H256::repeat_byte(1);
+		let closed = sp_core::H256::repeat_byte(2);
+		let old = |reveal_end: BlockNumber, payout: Balance, settled: bool| {
+			let votes = vec![(alice.clone(), 3u8, Vote::Yes)];
+			(
+				Data::Raw(BoundedVec::default()),
+				alice.clone(),
+				3u32,
+				0u32,
+				100u64,
+				Some(reveal_end),
+				votes,
+				vec![alice.clone()],
+				payout,
+				settled,
+			)
+		};
+		let key = <ProposalData<Test>>::hashed_key_for(&open);
+		frame_support::storage::unhashed::put(&key, &old(150, 0, false));
+		let key = <ProposalData<Test>>::hashed_key_for(&closed);
+		frame_support::storage::unhashed::put(&key, &old(110, 50, true));
+		<Proposals<Test>>::put(BoundedVec::try_from(vec![open, closed]).unwrap());
+		let _ = QuadraticVoting::decrease_votes(&alice, 3_u8.pow(2));
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(<Proposals<Test>>::get().into_inner(), vec![open]);
+		let archived = <Archive<Test>>::get(closed, 110).unwrap();
+		assert_eq!(archived.outcome, Outcome::Approved);
+		assert_eq!(archived.payout, 50);
+		let proposal = <ProposalData<Test>>::get(open).unwrap();
+		assert_eq!(proposal.track, 0);
+		assert_eq!(proposal.reveal_end, Some(150));
+		assert_eq!(proposal.ayes, 3);
+		assert_eq!(<Schedule<Test>>::get(150).into_inner(), vec![Transition::CloseReveal(open)]);
+		assert_eq!(<TrackProposals<Test>>::get(0), 1);
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), StorageVersion::new(1));
+
+		//the open proposal is closed on time and the voter gets the tokens back
+		run_to_block(150);
+		assert_eq!(<Archive<Test>>::get(open, 150).unwrap().outcome, Outcome::Approved);
+		assert_eq!(QuadraticVoting::pending_claims(&alice), vec![open]);
+		assert_ok!(QuadraticVoting::claim(origin, open, alice.clone()));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn signature_commits_dropped_and_refunded_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		// the commit in the old format, its tokens have been deducted on commit
+		let _ = QuadraticVoting::decrease_votes(&alice, 8_u8.pow(2));
+		let signature = sp_core::sr25519::Signature::from_raw([0u8; 64]);
+		let old_commit = (sp_runtime::MultiSignature::Sr25519(signature), 8u8, 10u32);
+		let key = <Commits<Test>>::hashed_key_for(&alice, &proposal_hash);
+		frame_support::storage::unhashed::put(&key, &old_commit);
+
+		migrations::v2::MigrateToV2::<Test, sp_runtime::MultiSignature>::on_runtime_upgrade();
+
+		assert!(<Commits<Test>>::get(&alice, &proposal_hash).is_none());
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), StorageVersion::new(2));
+	});
+}
+
+#[test]
+fn closed_proposal_archived_and_slot_freed() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::No, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::No, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin.clone(), proposal_hash));
+
+		assert!(<Proposals<Test>>::get().is_empty());
+		assert!(<ProposalData<Test>>::get(proposal_hash).is_none());
+
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Disapproved);
+		assert_eq!((archived.ayes, archived.nays, archived.revealed), (0, 3, 1));
+		assert_eq!(archived.closed_at, 160);
+
+		// the same text can be submitted again once it is settled
+		assert_ok!(QuadraticVoting::claim(origin.clone(), proposal_hash, alice.clone()));
+		let result = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		assert_ok!(result);
+		assert_eq!(<Proposals<Test>>::get().into_inner(), vec![proposal_hash]);
+	});
+}
+
+#[test]
+fn resubmission_waits_for_settlement() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let create = || {
+			QuadraticVoting::create_proposal(
+				origin_alice.clone(),
+				Box::new(Data::Raw(BoundedVec::default())),
+				0,
+				Threshold::SimpleMajority,
+				100,
+			)
+		};
+		assert_ok!(create());
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+
+		//the slash of bob and the reward of alice are still owed on the closed proposal
+		assert_noop!(create(), Error::<Test>::SettlementPending);
+
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, bob.clone()));
+		assert_noop!(create(), Error::<Test>::SettlementPending);
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		assert!(!<Settlements<Test>>::contains_key(proposal_hash));
+
+		assert_ok!(create());
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn slots_not_exhausted_by_closed_proposals() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		for round in 0..(MaxProposals::get() as u64 + 1) {
+			let start = round * 200;
+			System::set_block_number(start);
+			let text: Vec<u8> = vec![round as u8];
+			assert_ok!(QuadraticVoting::create_proposal(
+				origin.clone(),
+				Box::new(Data::Raw(text.try_into().unwrap())),
+				0,
+				Threshold::SimpleMajority,
+				100,
+			));
+			let proposal_hash = <Proposals<Test>>::get()[0];
+
+			System::set_block_number(start + 100);
+			assert_ok!(QuadraticVoting::close_vote(origin.clone(), proposal_hash));
+			System::set_block_number(start + 150);
+			assert_ok!(QuadraticVoting::close_reveal(origin.clone(), proposal_hash));
+		}
+		assert!(<Proposals<Test>>::get().is_empty());
+	});
+}
+
+#[test]
+fn prune_archive_after_retention() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		System::set_block_number(100);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		System::set_block_number(150);
+		let _ = QuadraticVoting::close_reveal(origin.clone(), proposal_hash);
+
+		let result = QuadraticVoting::prune_archive(origin.clone(), proposal_hash, 151);
+		assert_noop!(result, Error::<Test>::NotArchived);
+
+		let result = QuadraticVoting::prune_archive(origin.clone(), proposal_hash, 150);
+		assert_noop!(result, Error::<Test>::TooEarly);
+
+		System::set_block_number(150 + ArchiveRetention::get());
+		assert_ok!(QuadraticVoting::prune_archive(origin, proposal_hash, 150));
+		assert!(!<Archive<Test>>::contains_key(proposal_hash, 150));
+	});
+}
+
+#[test]
+fn queries_follow_proposal_phases() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		assert_eq!(QuadraticVoting::member(&alice), None);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		assert_eq!(
+			QuadraticVoting::member(&alice),
+			Some(VoterBalance {
+				voting_tokens: MaxTokens::get(),
+				reserved_balance: EntryFee::get(),
+				delegated_in: 0,
+				delegated_out: 0,
+			})
+		);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let poll_end = <ProposalData<Test>>::get(proposal_hash).unwrap().poll_end;
+		assert_eq!(QuadraticVoting::phase(&proposal_hash), Some(Phase::Commit { ends: poll_end }));
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		assert_eq!(QuadraticVoting::pending_reveals(&alice), vec![proposal_hash]);
+
+		run_to_block(poll_end);
+		let reveal_end = poll_end + general_track().reveal_length;
+		assert_eq!(QuadraticVoting::phase(&proposal_hash), Some(Phase::Reveal { ends: reveal_end }));
+
+		let _ = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, salt);
+		assert!(QuadraticVoting::pending_reveals(&alice).is_empty());
+
+		run_to_block(reveal_end);
+		assert_eq!(QuadraticVoting::phase(&proposal_hash), Some(Phase::Closed { at: reveal_end }));
+		assert_eq!(QuadraticVoting::archived(&proposal_hash).len(), 1);
+	});
+}
+
+#[test]
+fn default_policy_slashes_tenth_and_splits_equally() {
+	let loser = policy::Voter { who: 1u64, weight: 3, stake: 1_000u128 };
+	assert_eq!(<policy::DefaultPolicy as policy::SlashingPolicy<u64, u128>>::slash(&loser, 5), 100);
+
+	let winners = vec![
+		policy::Voter { who: 2u64, weight: 1, stake: 1_000u128 },
+		policy::Voter { who: 3u64, weight: 3, stake: 3_000u128 },
+	];
+	let shares: Vec<u128> = winners
+		.iter()
+		.map(<policy::DefaultPolicy as policy::SlashingPolicy<u64, u128>>::share)
+		.collect();
+	assert_eq!(shares, vec![1, 1]);
+}
+
+#[test]
+fn configurable_policy_splits_pro_rata() {
+	parameter_types! {
+		pub const Ratio: Perbill = Perbill::from_percent(25);
+		pub const ByWeight: policy::Split = policy::Split::ByWeight;
+		pub const ByStake: policy::Split = policy::Split::ByStake;
+	}
+	type WeightPolicy = policy::ConfigurablePolicy<Ratio, ByWeight, ConstBool<false>>;
+	type StakePolicy = policy::ConfigurablePolicy<Ratio, ByStake, ConstBool<false>>;
+
+	let loser = policy::Voter { who: 1u64, weight: 3, stake: 1_000u128 };
+	assert_eq!(<WeightPolicy as policy::SlashingPolicy<u64, u128>>::slash(&loser, 5), 250);
+	assert!(!<WeightPolicy as policy::SlashingPolicy<u64, u128>>::slash_on_tie());
+
+	let winners = vec![
+		policy::Voter { who: 2u64, weight: 1, stake: 3_000u128 },
+		policy::Voter { who: 3u64, weight: 3, stake: 1_000u128 },
+	];
+	let shares: Vec<u128> =
+		winners.iter().map(<WeightPolicy as policy::SlashingPolicy<u64, u128>>::share).collect();
+	assert_eq!(shares, vec![1, 3]);
+	let shares: Vec<u128> =
+		winners.iter().map(<StakePolicy as policy::SlashingPolicy<u64, u128>>::share).collect();
+	assert_eq!(shares, vec![3_000, 1_000]);
+}
+
+#[test]
+fn configurable_policy_scales_slash() {
+	parameter_types! {
+		pub const Ratio: Perbill = Perbill::from_percent(10);
+		pub const Equal: policy::Split = policy::Split::Equal;
+		pub const ByWeight: policy::SlashScale = policy::SlashScale::ByWeight;
+		pub const ByMargin: policy::SlashScale = policy::SlashScale::ByMargin;
+	}
+	type WeightPolicy = policy::ConfigurablePolicy<Ratio, Equal, ConstBool<false>, ByWeight>;
+	type MarginPolicy = policy::ConfigurablePolicy<Ratio, Equal, ConstBool<false>, ByMargin>;
+
+	let loser = policy::Voter { who: 1u64, weight: 3, stake: 1_000u128 };
+	assert_eq!(<WeightPolicy as policy::SlashingPolicy<u64, u128>>::slash(&loser, 5), 300);
+	assert_eq!(<MarginPolicy as policy::SlashingPolicy<u64, u128>>::slash(&loser, 5), 500);
+	//never more than the stake
+	assert_eq!(<MarginPolicy as policy::SlashingPolicy<u64, u128>>::slash(&loser, 20), 1_000);
+	let heavy = policy::Voter { weight: 10, ..loser.clone() };
+	assert_eq!(<WeightPolicy as policy::SlashingPolicy<u64, u128>>::slash(&heavy, 5), 1_000);
+}
+
+fn options(count: u8) -> Options {
+	(0..count)
+		.map(|index| Data::Raw(vec![index].try_into().unwrap()))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+fn choices(spread: &[(OptionIndex, VoteToken)]) -> Vote {
+	Vote::Options(spread.to_vec().try_into().unwrap())
+}
+
+#[test]
+fn multi_option_votes_spread_quadratically() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		assert_ok!(QuadraticVoting::create_multi_option_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			options(3),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//2 tokens on the first option and 1 on the third cost 2^2 + 1^2
+		let alice_vote = choices(&[(0, 2), (2, 1)]);
+		let (commitment, salt) = generate(&alice, proposal_hash, alice_vote.clone(), 5);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			5,
+			Conviction::None,
+		));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 5);
+
+		let bob_vote = choices(&[(1, 1)]);
+		let (commitment, _) = generate(&bob, proposal_hash, bob_vote.clone(), 1);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		));
+
+		System::set_block_number(101);
+		assert_ok!(QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash));
+		assert_ok!(QuadraticVoting::reveal_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			alice_vote,
+			salt
+		));
+		assert_ok!(QuadraticVoting::reveal_vote(origin_bob, proposal_hash, bob_vote, salt));
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().tallies, vec![2, 1, 1]);
+
+		System::set_block_number(160);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Chosen {
+			proposal_hash,
+			option: 0,
+		}));
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Chosen(0));
+		assert_eq!(archived.tallies, vec![2, 1, 1]);
+
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, bob.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, alice.clone()));
+		let slash = bob_balance - <Members<Test>>::get(&bob).reserved_balance;
+		assert!(slash > 0);
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance + slash);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn multi_option_losers_weigh_tokens_not_cost() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_multi_option_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			options(2),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let alice_vote = choices(&[(0, 3)]);
+		let (commitment, salt) = generate(&alice, proposal_hash, alice_vote.clone(), 9);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			9,
+			Conviction::None,
+		);
+		//2 tokens cost 4
+		let bob_vote = choices(&[(1, 2)]);
+		let (commitment, _) = generate(&bob, proposal_hash, bob_vote.clone(), 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice, proposal_hash, alice_vote, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, bob_vote, salt);
+
+		let proposal_data = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		let (_, _, winners, losers) = QuadraticVoting::deduce_outcome(&proposal_data);
+		assert_eq!(winners, vec![(alice, 3)]);
+		assert_eq!(losers, vec![(bob, 2)]);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn invalid_options_rejected() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		//a single option is not a choice
+		assert_noop!(
+			QuadraticVoting::create_multi_option_proposal(
+				origin.clone(),
+				Box::new(Data::Raw(BoundedVec::default())),
+				options(1),
+				0,
+				Threshold::SimpleMajority,
+				100,
+			),
+			Error::<Test>::InvalidOptions
+		);
+
+		let _ = QuadraticVoting::create_multi_option_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			options(2),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//the committed number does not match the sum of squares
+		let vote = choices(&[(0, 2), (1, 1)]);
+		let (commitment, salt) = generate(&alice, proposal_hash, vote.clone(), 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+		assert_noop!(
+			QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, vote, salt),
+			Error::<Test>::InvalidOptions
+		);
+	});
+}
+
+#[test]
+fn tied_options_give_tie() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_multi_option_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			options(2),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let alice_vote = choices(&[(0, 2)]);
+		let (commitment, salt) = generate(&alice, proposal_hash, alice_vote.clone(), 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+		let bob_vote = choices(&[(1, 2)]);
+		let (commitment, _) = generate(&bob, proposal_hash, bob_vote.clone(), 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, alice_vote, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, bob_vote, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice, proposal_hash));
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 160).unwrap().outcome, Outcome::Tie);
+	});
+}
+
+#[test]
+fn abstainers_neither_rewarded_nor_slashed() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		//charlie cannot afford the stake with the genesis balance
+		let charlie = get_charlie();
+		let origin_charlie = Origin::signed(charlie.clone());
+		let _ = Balances::set_balance(Origin::root(), charlie.clone(), 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_charlie.clone(), Box::new(data()));
+		assert_ok!(QuadraticVoting::join_committee(origin_charlie.clone()));
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&charlie, proposal_hash, Vote::Abstain, 2);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_charlie.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		));
+		assert_eq!(<Members<Test>>::get(&charlie).voting_tokens, MaxTokens::get() - 4);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, salt);
+		assert_ok!(QuadraticVoting::reveal_vote(origin_charlie, proposal_hash, Vote::Abstain, salt));
+
+		System::set_block_number(160);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		let charlie_balance = <Members<Test>>::get(&charlie).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Approved);
+		assert_eq!((archived.ayes, archived.nays, archived.abstains), (3, 2, 2));
+		assert_eq!(archived.revealed, 3);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::ClosedReveal {
+			proposal_hash,
+			yes: 3,
+			no: 2,
+			abstain: 2,
+			revealed: 3,
+		}));
+
+		for voter in [&bob, &charlie, &alice] {
+			assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, voter.clone()));
+		}
+
+		//the whole slash of bob goes to alice
+		let slash = bob_balance - <Members<Test>>::get(&bob).reserved_balance;
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance + slash);
+		assert_eq!(<Members<Test>>::get(&charlie).reserved_balance, charlie_balance);
+		assert_eq!(<Members<Test>>::get(&charlie).voting_tokens, MaxTokens::get());
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn proposal_without_quorum_fails() {
+	new_test_ext().execute_with(|| {
+		let mut track = general_track();
+		track.quorum = Quorum::Votes(5);
+		Tracks::set(vec![(0, track)]);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 1);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, salt);
+
+		System::set_block_number(160);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, bob.clone()));
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::NoQuorum(proposal_hash)));
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::NoQuorum);
+		assert_eq!(archived.payout, 0);
+
+		//neither side is slashed nor rewarded
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance);
+		assert_eq!(<Members<Test>>::get(&bob).reserved_balance, bob_balance);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn majority_short_of_supermajority_rejects() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		assert_ok!(QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SuperMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//3 of 5 votes fall short of two thirds
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, salt);
+
+		System::set_block_number(160);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, bob.clone()));
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Rejected(proposal_hash)));
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Rejected);
+
+		//the majority is not punished for falling short, nor the minority rewarded
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance);
+		assert_eq!(<Members<Test>>::get(&bob).reserved_balance, bob_balance);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn leading_option_short_of_supermajority_rejects() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		assert_ok!(QuadraticVoting::create_multi_option_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			options(2),
+			0,
+			Threshold::SuperMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//3 of 5 votes fall short of two thirds
+		let alice_vote = choices(&[(0, 3)]);
+		let (commitment, salt) = generate(&alice, proposal_hash, alice_vote.clone(), 9);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			9,
+			Conviction::None,
+		);
+		let bob_vote = choices(&[(1, 2)]);
+		let (commitment, _) = generate(&bob, proposal_hash, bob_vote.clone(), 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, alice_vote, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, bob_vote, salt);
+
+		System::set_block_number(160);
+		let alice_balance = <Members<Test>>::get(&alice).reserved_balance;
+		let bob_balance = <Members<Test>>::get(&bob).reserved_balance;
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash));
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, bob.clone()));
+
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Rejected);
+		//nobody is slashed, as with yes or no
+		assert_eq!(<Members<Test>>::get(&alice).reserved_balance, alice_balance);
+		assert_eq!(<Members<Test>>::get(&bob).reserved_balance, bob_balance);
+	});
+}
+
+#[test]
+fn quorum_of_members_counted_at_creation() {
+	new_test_ext().execute_with(|| {
+		let quorum = Quorum::Members(Perbill::from_percent(50));
+		Tracks::set(vec![(0, TrackInfo { quorum, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		assert_ok!(QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		//members joining later do not raise the quorum
+		let charlie = get_charlie();
+		let origin_charlie = Origin::signed(charlie.clone());
+		let _ = Balances::set_balance(Origin::root(), charlie, 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_charlie.clone(), Box::new(data()));
+		assert_ok!(QuadraticVoting::join_committee(origin_charlie));
+		let dave = get_account_id_from_seed::<sp_core::sr25519::Public>("Dave");
+		let origin_dave = Origin::signed(dave.clone());
+		let _ = Balances::set_balance(Origin::root(), dave, 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_dave.clone(), Box::new(data()));
+		assert_ok!(QuadraticVoting::join_committee(origin_dave));
+
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice, proposal_hash));
+
+		//1 of the 2 members at creation is enough
+		let archived = <Archive<Test>>::get(proposal_hash, 160).unwrap();
+		assert_eq!(archived.outcome, Outcome::Approved);
+	});
+}
+
+#[test]
+fn threshold_must_be_allowed() {
+	new_test_ext().execute_with(|| {
+		let mut track = general_track();
+		track.thresholds = vec![Threshold::SimpleMajority];
+		Tracks::set(vec![(0, track)]);
+
+		let origin = Origin::signed(get_alice());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		assert_noop!(
+			QuadraticVoting::create_proposal(
+				origin,
+				Box::new(Data::Raw(BoundedVec::default())),
+				0,
+				Threshold::Unanimity,
+				100,
+			),
+			Error::<Test>::ThresholdNotAllowed
+		);
+	});
+}
+
+#[test]
+fn thresholds_measure_votes_for_against() {
+	assert!(Threshold::SimpleMajority.approves(3, 2));
+	assert!(!Threshold::SimpleMajority.approves(2, 2));
+	assert!(Threshold::SuperMajority.approves(2, 1));
+	assert!(!Threshold::SuperMajority.approves(3, 2));
+	assert!(Threshold::Unanimity.approves(1, 0));
+	assert!(!Threshold::Unanimity.approves(9, 1));
+	assert!(!Threshold::Unanimity.approves(0, 0));
+}
+
+#[test]
+fn proposal_follows_its_track() {
+	new_test_ext().execute_with(|| {
+		let fast = TrackInfo {
+			min_length: 10,
+			max_length: 20,
+			reveal_length: 5,
+			deposit: 100,
+			max_proposals: 1,
+			..general_track()
+		};
+		Tracks::set(vec![(0, general_track()), (1, fast)]);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let reserved = Balances::reserved_balance(&alice);
+
+		let create = |text: u8, track: TrackId, duration: BlockNumber| {
+			QuadraticVoting::create_proposal(
+				origin.clone(),
+				Box::new(Data::Raw(vec![text].try_into().unwrap())),
+				track,
+				Threshold::SimpleMajority,
+				duration,
+			)
+		};
+		assert_noop!(create(0, 7, 10), Error::<Test>::BadTrack);
+		assert_noop!(create(0, 1, 9), Error::<Test>::WrongProposalLength);
+		assert_noop!(create(0, 1, 21), Error::<Test>::WrongProposalLength);
+
+		assert_ok!(create(0, 1, 10));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().track, 1);
+		assert_eq!(Balances::reserved_balance(&alice), reserved + 100);
+		assert_noop!(create(1, 1, 10), Error::<Test>::TrackFull);
+		//other tracks are not affected
+		assert_ok!(create(1, 0, 100));
+
+		run_to_block(11);
+		assert_eq!(QuadraticVoting::phase(&proposal_hash), Some(Phase::Reveal { ends: 15 }));
+
+		run_to_block(15);
+		assert_eq!(QuadraticVoting::archived(&proposal_hash)[0].track, 1);
+		assert_eq!(<TrackProposals<Test>>::get(1), 0);
+	});
+}
+
+#[test]
+fn deposit_refunded_once_votes_revealed() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let reserved = Balances::reserved_balance(&alice);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().deposit, 100);
+		assert_eq!(Balances::reserved_balance(&alice), reserved + 100);
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, salt);
+		run_to_block(151);
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::DepositRefunded {
+			proposal_hash,
+			who: alice.clone(),
+			amount: 100,
+		}));
+		assert_eq!(Balances::reserved_balance(&alice), reserved);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn deposit_slashed_without_turnout() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let reserved = Balances::reserved_balance(&alice);
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		//the deposit is held apart from the stake
+		let deposit_id = QuadraticVoting::deposit_reserve_id();
+		assert_eq!(Balances::reserved_balance_named(&deposit_id, &alice), 100);
+		run_to_block(151);
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::DepositSlashed {
+			proposal_hash,
+			who: alice.clone(),
+			amount: 100,
+		}));
+		assert_eq!(Balances::reserved_balance(&alice), reserved);
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance + 100);
+		let id = QuadraticVoting::reserve_id();
+		assert_eq!(Balances::reserved_balance_named(&id, &alice), EntryFee::get());
+		assert_eq!(QuadraticVoting::stake_of(&alice), EntryFee::get());
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn deposit_slashed_without_quorum() {
+	new_test_ext().execute_with(|| {
+		let track = TrackInfo { deposit: 100, quorum: Quorum::Votes(5), ..general_track() };
+		Tracks::set(vec![(0, track)]);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let reserved = Balances::reserved_balance(&alice);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//2 revealed votes fall short of the quorum of 5
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		run_to_block(101);
+		assert_ok!(QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, salt));
+		run_to_block(151);
+
+		assert_eq!(QuadraticVoting::archived(&proposal_hash)[0].outcome, Outcome::NoQuorum);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::DepositSlashed {
+			proposal_hash,
+			who: alice.clone(),
+			amount: 100,
+		}));
+		assert_eq!(Balances::reserved_balance(&alice), reserved);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn proposer_cancels_before_commits() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+		let reserved = Balances::reserved_balance(&alice);
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		System::set_block_number(1);
+
+		assert_noop!(
+			QuadraticVoting::cancel_proposal(origin_bob, proposal_hash),
+			Error::<Test>::NotProposer
+		);
+		assert_ok!(QuadraticVoting::cancel_proposal(origin_alice, proposal_hash));
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Cancelled(proposal_hash)));
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 1).unwrap().outcome, Outcome::Cancelled);
+		assert!(<Proposals<Test>>::get().is_empty());
+		assert_eq!(<TrackProposals<Test>>::get(0), 0);
+		assert_eq!(Balances::reserved_balance(&alice), reserved);
+		assert!(<Schedule<Test>>::get(100).is_empty());
+	});
+}
+
+#[test]
+fn cannot_cancel_after_commits() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		assert_noop!(
+			QuadraticVoting::cancel_proposal(origin, proposal_hash),
+			Error::<Test>::VotesCommitted
+		);
+	});
+}
+
+#[test]
+fn force_cancel_refunds_voters() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+		let bob_reserved = Balances::reserved_balance(&bob);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let alice_reserved = Balances::reserved_balance(&alice);
+
+		assert_noop!(
+			QuadraticVoting::force_cancel(origin_alice, proposal_hash, false),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(QuadraticVoting::force_cancel(Origin::root(), proposal_hash, false));
+
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Cancelled(proposal_hash)));
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 101).unwrap().outcome, Outcome::Cancelled);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+		assert_eq!(<Members<Test>>::get(&bob).voting_tokens, MaxTokens::get());
+		assert!(<Commits<Test>>::get(&bob, proposal_hash).is_none());
+		//nobody is slashed and the deposit is released
+		assert_eq!(Balances::reserved_balance(&alice), alice_reserved - 100);
+		assert_eq!(Balances::reserved_balance(&bob), bob_reserved);
+
+		//the closing transition is gone
+		run_to_block(151);
+		assert_eq!(QuadraticVoting::archived(&proposal_hash).len(), 1);
+		assert_ok!(QuadraticVoting::leave_committee(origin_bob));
+	});
+}
+
+#[test]
+fn spam_deposit_slashed_on_force_cancel() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let reserved = Balances::reserved_balance(&alice);
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		assert_ok!(QuadraticVoting::force_cancel(Origin::root(), proposal_hash, true));
+		assert_eq!(Balances::reserved_balance(&alice), reserved);
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance + 100);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn kicked_member_forfeits_stake_and_votes() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(vec![1].try_into().unwrap())),
+			0,
+			Threshold::SimpleMajority,
+			200,
+		);
+		let pending_hash = <Proposals<Test>>::get()[1];
+
+		let (commitment, salt) = generate(&bob, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, pending_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			pending_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::Yes, salt);
+
+		assert_noop!(
+			QuadraticVoting::kick_member(origin_alice, bob.clone(), Perbill::from_percent(50)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(QuadraticVoting::kick_member(
+			Origin::root(),
+			bob.clone(),
+			Perbill::from_percent(50)
+		));
+
+		let forfeited = EntryFee::get() / 2;
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Kicked {
+			who: bob.clone(),
+			forfeited,
+		}));
+		assert!(!QuadraticVoting::is_member(&bob));
+		assert_eq!(Balances::reserved_balance(&bob), 0);
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance + forfeited);
+
+		//the votes of the member no longer count
+		assert!(<Commits<Test>>::get(&bob, pending_hash).is_none());
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!(proposal.ayes, 0);
+		assert!(proposal.votes.is_empty());
+		assert!(!proposal.revealed.contains(&bob));
+		assert!(!<ProposalData<Test>>::get(pending_hash).unwrap().committed.contains(&bob));
+
+		//closing the proposals does not bring the member back
+		run_to_block(300);
+		assert!(!QuadraticVoting::is_member(&bob));
+		assert_noop!(
+			QuadraticVoting::kick_member(Origin::root(), bob, Perbill::zero()),
+			Error::<Test>::NotMember
+		);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+
+#[test]
+fn kicked_member_commit_no_longer_counted() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_bob,
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		));
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!((proposal.voters, proposal.turnout, proposal.unrevealed), (1, 1, 1));
+
+		assert_ok!(QuadraticVoting::kick_member(Origin::root(), bob.clone(), Perbill::zero()));
+
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!((proposal.voters, proposal.turnout, proposal.unrevealed), (0, 0, 0));
+		assert!(proposal.committed.is_empty());
+		assert!(!<Turnout<Test>>::contains_key(proposal_hash, &bob));
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn banned_account_rejoins_after_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob = get_bob();
+		let origin = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		assert_ok!(QuadraticVoting::ban_member(Origin::root(), bob.clone(), 10, Perbill::zero()));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Banned {
+			who: bob.clone(),
+			until: 10,
+		}));
+		assert!(!QuadraticVoting::is_member(&bob));
+		assert_eq!(Balances::reserved_balance(&bob), 0);
+
+		assert_noop!(QuadraticVoting::join_committee(origin.clone()), Error::<Test>::Banned);
+		run_to_block(10);
+		assert_ok!(QuadraticVoting::join_committee(origin));
+		assert!(<Bans<Test>>::get(&bob).is_none());
+	});
+}
+
+#[test]
+fn force_added_member_needs_no_identity() {
+	new_test_ext().execute_with(|| {
+		let bob = get_bob();
+		assert_noop!(
+			QuadraticVoting::force_add_member(Origin::signed(bob.clone()), bob.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(QuadraticVoting::force_add_member(Origin::root(), bob.clone()));
+
+		assert!(QuadraticVoting::is_member(&bob));
+		assert_eq!(<Members<Test>>::get(&bob).voting_tokens, MaxTokens::get());
+		assert_eq!(Balances::reserved_balance(&bob), EntryFee::get());
+		assert_noop!(
+			QuadraticVoting::force_add_member(Origin::root(), bob.clone()),
+			Error::<Test>::AlreadyMember
+		);
+		assert_noop!(
+			QuadraticVoting::force_add_member(Origin::root(), get_charlie()),
+			Error::<Test>::NotEnoughFunds
+		);
+
+		//the member can propose and vote, and is waited for
+		System::set_block_number(1);
+		let origin = Origin::signed(bob.clone());
+		assert_ok!(QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		));
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		assert_ok!(QuadraticVoting::refresh_identity(origin.clone(), bob.clone()));
+		assert!(!<Suspensions<Test>>::contains_key(&bob));
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::Yes, 1);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::CommitComplete(
+			proposal_hash,
+		)));
+		assert_ok!(QuadraticVoting::close_vote(origin, proposal_hash));
+	});
+}
+
+#[test]
+fn joining_requires_minimum_judgement() {
+	new_test_ext().execute_with(|| {
+		MinJudgement::set(IdentityLevel::Reasonable);
+
+		let alice = get_alice();
+		let bob = get_bob();
+		let origin = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+
+		//a self-set identity is not enough
+		assert_noop!(
+			QuadraticVoting::join_committee(origin.clone()),
+			Error::<Test>::InsufficientJudgement
+		);
+
+		let _ = Identity::add_registrar(Origin::root(), alice.clone());
+		let _ = Identity::provide_judgement(
+			Origin::signed(alice),
+			0,
+			bob.clone(),
+			Judgement::Reasonable,
+		);
+		assert_ok!(QuadraticVoting::join_committee(origin));
+		assert!(QuadraticVoting::is_member(&bob));
+	});
+}
+
+#[test]
+fn member_without_identity_is_suspended() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let _ = Identity::clear_identity(origin_bob.clone());
+		let (commitment, salt) = generate(&bob, proposal_hash, Vote::Yes, 3);
+		assert_noop!(
+			QuadraticVoting::commit_vote(
+				origin_bob.clone(),
+				proposal_hash,
+				commitment,
+				3,
+				Conviction::None,
+			),
+			Error::<Test>::Suspended
+		);
+		assert_noop!(
+			QuadraticVoting::create_proposal(
+				origin_bob.clone(),
+				Box::new(Data::Raw(vec![1].try_into().unwrap())),
+				0,
+				Threshold::SimpleMajority,
+				100,
+			),
+			Error::<Test>::Suspended
+		);
+
+		//anyone can record the suspension
+		System::set_block_number(1);
+		assert_ok!(QuadraticVoting::refresh_identity(origin_alice.clone(), bob.clone()));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Suspended(bob.clone())));
+		assert!(<Suspensions<Test>>::contains_key(&bob));
+
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		assert_ok!(QuadraticVoting::refresh_identity(origin_alice, bob.clone()));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Reinstated(bob.clone())));
+		assert!(!<Suspensions<Test>>::contains_key(&bob));
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		));
+
+		//a suspended member can still reveal and cash out
+		let _ = Identity::clear_identity(origin_bob.clone());
+		run_to_block(101);
+		assert_ok!(QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::Yes, salt));
+		run_to_block(151);
+		assert_ok!(QuadraticVoting::leave_committee(origin_bob));
+		assert_eq!(Balances::reserved_balance(&bob), 0);
+	});
+}
+
+#[test]
+fn delegate_carries_votes_of_delegators() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let charlie = get_charlie();
+		let origin_charlie = Origin::signed(charlie.clone());
+		let _ = Balances::set_balance(Origin::root(), charlie.clone(), 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_charlie.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_charlie.clone());
+
+		System::set_block_number(1);
+		assert_ok!(QuadraticVoting::delegate(origin_charlie.clone(), bob.clone(), None));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Delegated {
+			from: charlie.clone(),
+			to: bob.clone(),
+			track: None,
+		}));
+		assert_eq!(QuadraticVoting::member(&bob).unwrap().delegated_in, MaxTokens::get() as u32);
+		assert_eq!(QuadraticVoting::member(&charlie).unwrap().delegated_out, MaxTokens::get());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//the delegator votes through the delegate
+		let (commitment, _) = generate(&charlie, proposal_hash, Vote::Yes, 2);
+		assert_noop!(
+			QuadraticVoting::commit_vote(
+				origin_charlie,
+				proposal_hash,
+				commitment,
+				2,
+				Conviction::None,
+			),
+			Error::<Test>::Delegated
+		);
+
+		let (commitment, alice_salt) = generate(&alice, proposal_hash, Vote::Yes, 5);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			5,
+			Conviction::None,
+		);
+		let (commitment, bob_salt) = generate(&bob, proposal_hash, Vote::No, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		assert_eq!(<Members<Test>>::get(&charlie).voting_tokens, MaxTokens::get() - 9);
+
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin_alice, proposal_hash, Vote::Yes, alice_salt);
+		assert_ok!(QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, bob_salt));
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!((proposal.ayes, proposal.nays), (5, 6));
+		assert!(proposal.revealed.contains(&charlie));
+
+		//the delegator shares the reward of the delegate's side
+		run_to_block(151);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Disapproved(proposal_hash)));
+		assert_eq!(QuadraticVoting::pending_claims(&charlie), vec![proposal_hash]);
+		for voter in [&alice, &bob, &charlie] {
+			let origin = Origin::signed(alice.clone());
+			assert_ok!(QuadraticVoting::claim(origin, proposal_hash, voter.clone()));
+		}
+		let reward = EntryFee::get() / 10 / 2;
+		assert_eq!(Balances::reserved_balance(&charlie), EntryFee::get() + reward);
+		assert_eq!(<Members<Test>>::get(&charlie).voting_tokens, MaxTokens::get());
+	});
+}
+
+#[test]
+fn delegations_are_a_single_hop() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let charlie = get_charlie();
+		let origin_charlie = Origin::signed(charlie.clone());
+		let _ = Balances::set_balance(Origin::root(), charlie.clone(), 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_charlie.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_charlie.clone());
+
+		assert_noop!(
+			QuadraticVoting::delegate(origin_bob.clone(), bob.clone(), None),
+			Error::<Test>::InvalidDelegate
+		);
+		assert_noop!(
+			QuadraticVoting::delegate(origin_bob.clone(), alice.clone(), Some(9)),
+			Error::<Test>::BadTrack
+		);
+
+		assert_ok!(QuadraticVoting::delegate(origin_charlie.clone(), bob.clone(), None));
+		//a delegate cannot delegate and a delegator cannot be delegated to
+		assert_noop!(
+			QuadraticVoting::delegate(origin_bob, alice.clone(), Some(0)),
+			Error::<Test>::DelegationChain
+		);
+		assert_noop!(
+			QuadraticVoting::delegate(origin_alice, charlie.clone(), None),
+			Error::<Test>::DelegationChain
+		);
+
+		//a delegation on a track takes precedence
+		assert_ok!(QuadraticVoting::delegate(origin_charlie.clone(), alice.clone(), Some(0)));
+		assert_eq!(QuadraticVoting::delegate_of(&charlie, 0), Some(alice.clone()));
+		assert!(QuadraticVoting::delegators_of(&bob, 0).is_empty());
+		assert_eq!(QuadraticVoting::delegators_of(&alice, 0), vec![charlie.clone()]);
+
+		assert_ok!(QuadraticVoting::undelegate(origin_charlie.clone(), Some(0)));
+		assert_eq!(QuadraticVoting::delegate_of(&charlie, 0), Some(bob));
+		assert_ok!(QuadraticVoting::undelegate(origin_charlie.clone(), None));
+		assert_eq!(QuadraticVoting::delegate_of(&charlie, 0), None);
+		assert_noop!(
+			QuadraticVoting::undelegate(origin_charlie, None),
+			Error::<Test>::NotDelegating
+		);
+	});
+}
+
+#[test]
+fn delegators_not_penalised_for_unrevealed_delegate() {
+	new_test_ext().execute_with(|| {
+		//without quorum what the delegate loses stays in the pot
+		Tracks::set(vec![(0, TrackInfo { quorum: Quorum::Votes(1), ..general_track() })]);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		assert_ok!(QuadraticVoting::delegate(origin_alice.clone(), bob.clone(), Some(0)));
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::Yes, 4);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob,
+			proposal_hash,
+			commitment,
+			4,
+			Conviction::None,
+		);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 16);
+
+		run_to_block(151);
+		let origin = Origin::signed(alice.clone());
+		assert_ok!(QuadraticVoting::claim(origin, proposal_hash, bob.clone()));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get());
+		assert!(Balances::reserved_balance(&bob) < EntryFee::get());
+	});
+}
+
+#[test]
+fn conviction_multiplies_vote_weight() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//the conviction does not change the quadratic cost
+		let (commitment, alice_salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::Locked2x,
+		));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 9);
+		let (commitment, bob_salt) = generate(&bob, proposal_hash, Vote::No, 5);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			5,
+			Conviction::None,
+		);
+
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin_alice, proposal_hash, Vote::Yes, alice_salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, bob_salt);
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!((proposal.ayes, proposal.nays), (9, 5));
+
+		run_to_block(151);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Approved(proposal_hash)));
+	});
+}
+
+#[test]
+fn conviction_locks_stake_after_close() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::Locked2x,
+		);
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), proposal_hash, Vote::Yes, salt);
+		assert_eq!(QuadraticVoting::locked_until(&alice), None);
+
+		//closed at block 150, locked for two proposal lengths once claimed
+		run_to_block(151);
+		assert_eq!(QuadraticVoting::locked_until(&alice), None);
+		assert_ok!(QuadraticVoting::claim(origin.clone(), proposal_hash, alice.clone()));
+		assert_eq!(QuadraticVoting::locked_until(&alice), Some(350));
+		assert_noop!(
+			QuadraticVoting::leave_committee(origin.clone()),
+			Error::<Test>::ConvictionLocked
+		);
+
+		run_to_block(350);
+		assert_ok!(QuadraticVoting::leave_committee(origin));
+		assert_eq!(QuadraticVoting::locked_until(&alice), None);
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+	});
+}
+
+#[test]
+fn winners_claim_once_slashes_are_in() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		);
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		let _ = QuadraticVoting::reveal_vote(origin_bob.clone(), proposal_hash, Vote::No, salt);
+
+		//nothing is slashed or paid on close
+		run_to_block(151);
+		assert_eq!(Balances::reserved_balance(&bob), EntryFee::get());
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 150).unwrap().payout, 0);
+		assert_eq!(QuadraticVoting::pending_claims(&alice), vec![proposal_hash]);
+
+		//the winner gets the tokens back but waits for the loser
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get());
+		assert_noop!(
+			QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()),
+			Error::<Test>::SlashesPending
+		);
+
+		//anyone can claim for the loser
+		let slash = EntryFee::get() / 10;
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, bob.clone()));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::Claimed {
+			who: bob.clone(),
+			proposal_hash,
+			slashed: slash,
+			reward: 0,
+		}));
+		assert_eq!(Balances::reserved_balance(&bob), EntryFee::get() - slash);
+		assert_noop!(
+			QuadraticVoting::claim(origin_bob, proposal_hash, bob),
+			Error::<Test>::NothingToClaim
+		);
+
+		assert_ok!(QuadraticVoting::claim(origin_alice.clone(), proposal_hash, alice.clone()));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Settled {
+			proposal_hash,
+			payout: slash,
+		}));
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get() + slash);
+		assert_eq!(<Archive<Test>>::get(proposal_hash, 150).unwrap().payout, slash);
+		assert!(!<Settlements<Test>>::contains_key(proposal_hash));
+		assert!(QuadraticVoting::pending_claims(&alice).is_empty());
+		assert_noop!(
+			QuadraticVoting::claim(origin_alice, proposal_hash, alice),
+			Error::<Test>::NothingToClaim
+		);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn closed_votes_claimed_on_next_commit() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let closed_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, closed_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			closed_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		run_to_block(101);
+		let _ = QuadraticVoting::reveal_vote(origin.clone(), closed_hash, Vote::Yes, salt);
+		run_to_block(151);
+		assert_eq!(QuadraticVoting::pending_claims(&alice), vec![closed_hash]);
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 4);
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(vec![1].try_into().unwrap())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 3);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin,
+			proposal_hash,
+			commitment,
+			3,
+			Conviction::None,
+		));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::Settled {
+			proposal_hash: closed_hash,
+			payout: 0,
+		}));
+		assert!(QuadraticVoting::pending_claims(&alice).is_empty());
+		assert_eq!(<Members<Test>>::get(&alice).voting_tokens, MaxTokens::get() - 9);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn voters_per_proposal_bounded() {
+	new_test_ext().execute_with(|| {
+		MaxVoters::set(1);
+
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+
+		let bob = get_bob();
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice,
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		));
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 2);
+		assert_noop!(
+			QuadraticVoting::commit_vote(
+				origin_bob,
+				proposal_hash,
+				commitment,
+				2,
+				Conviction::None,
+			),
+			Error::<Test>::TooManyVoters
+		);
+	});
+}
+
+#[test]
+fn rounding_dust_rolled_over_to_next_reward() {
+	new_test_ext().execute_with(|| {
+		let dave = get_account_id_from_seed::<sp_core::sr25519::Public>("Dave");
+		let voters = [get_alice(), get_bob(), get_charlie(), dave.clone()];
+		for voter in voters.iter() {
+			let origin = Origin::signed(voter.clone());
+			let _ = Balances::set_balance(Origin::root(), voter.clone(), 1_000_000 * UNIT, 0);
+			let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+			assert_ok!(QuadraticVoting::join_committee(origin));
+		}
+		let origin = Origin::signed(dave.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//three winners split the slash of dave
+		let mut salt = [0u8; 32];
+		for voter in voters.iter() {
+			let vote = if voter == &dave { Vote::No } else { Vote::Yes };
+			let (commitment, voter_salt) = generate(voter, proposal_hash, vote, 1);
+			salt = voter_salt;
+			let _ = QuadraticVoting::commit_vote(
+				Origin::signed(voter.clone()),
+				proposal_hash,
+				commitment,
+				1,
+				Conviction::None,
+			);
+		}
+		run_to_block(101);
+		for voter in voters.iter() {
+			let vote = if voter == &dave { Vote::No } else { Vote::Yes };
+			let _ = QuadraticVoting::reveal_vote(
+				Origin::signed(voter.clone()),
+				proposal_hash,
+				vote,
+				salt,
+			);
+		}
+		run_to_block(151);
+
+		for voter in voters.iter().rev() {
+			assert_ok!(QuadraticVoting::claim(origin.clone(), proposal_hash, voter.clone()));
+			assert_ok!(QuadraticVoting::check_pot());
+		}
+		let slash = EntryFee::get() / 10;
+		let reward = Perbill::from_rational(1u32, 3) * slash;
+		let dust = slash - 3 * reward;
+		assert!(dust > 0);
+		assert_eq!(Balances::reserved_balance(&get_alice()), EntryFee::get() + reward);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::LeftoverCarried {
+			amount: dust,
+		}));
+		assert_eq!(<CarryOver<Test>>::get(), dust);
+		assert!(!<PotLedger<Test>>::contains_key(proposal_hash));
+
+		//the dust is paid to the winner of the next proposal
+		let alice = get_alice();
+		let origin_alice = Origin::signed(alice.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(vec![1].try_into().unwrap())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 1);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		);
+		run_to_block(252);
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		run_to_block(302);
+		assert_eq!(<CarryOver<Test>>::get(), 0);
+		assert_eq!(<PotLedger<Test>>::get(proposal_hash), dust);
+
+		assert_ok!(QuadraticVoting::claim(origin_alice, proposal_hash, alice.clone()));
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get() + reward + dust);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn late_reveal_slash_burned_when_configured() {
+	new_test_ext().execute_with(|| {
+		LeftoverRule::set(Leftover::Burn);
+
+		let alice = get_alice();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		System::set_block_number(101);
+		let _ = QuadraticVoting::close_vote(origin.clone(), proposal_hash);
+
+		//the reveal phase is over but the proposal is not closed yet
+		System::set_block_number(160);
+		let issuance = Balances::total_issuance();
+		assert_ok!(QuadraticVoting::reveal_vote(origin, proposal_hash, Vote::Yes, salt));
+
+		let slash = EntryFee::get() / 10;
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::LeftoverBurned {
+			amount: slash,
+		}));
+		assert_eq!(Balances::total_issuance(), issuance - slash);
+		assert_eq!(<CarryOver<Test>>::get(), 0);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn forfeited_stake_sent_to_treasury_when_configured() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let charlie = get_charlie();
+		LeftoverRule::set(Leftover::Treasury(charlie.clone()));
+
+		let bob = get_bob();
+		let origin = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin);
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+		let treasury_balance = Balances::free_balance(&charlie);
+
+		assert_ok!(QuadraticVoting::kick_member(
+			Origin::root(),
+			bob.clone(),
+			Perbill::from_percent(50)
+		));
+
+		let forfeited = EntryFee::get() / 2;
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::LeftoverTransferred {
+			to: charlie.clone(),
+			amount: forfeited,
+		}));
+		assert_eq!(Balances::free_balance(&charlie), treasury_balance + forfeited);
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn pot_carried_over_on_upgrade() {
+	new_test_ext().execute_with(|| {
+		let pot = QuadraticVoting::account_id();
+		let id = QuadraticVoting::reserve_id();
+		let _ = Balances::set_balance(Origin::root(), pot.clone(), 1_000, 0);
+		assert_ok!(Balances::reserve_named(&id, &pot, 300));
+		assert!(QuadraticVoting::check_pot().is_err());
+		StorageVersion::new(4).put::<QuadraticVoting>();
+
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(<CarryOver<Test>>::get(), 300);
+		assert_ok!(QuadraticVoting::check_pot());
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), StorageVersion::new(5));
+	});
+}
+
+#[test]
+fn slashed_funds_kept_by_pot() {
+	new_test_ext().execute_with(|| {
+		let bob = get_bob();
+		let pot = QuadraticVoting::account_id();
+		let id = QuadraticVoting::reserve_id();
+		let issuance = Balances::total_issuance();
+		let (slashed, _) = Balances::slash(&bob, 1_000);
+
+		<rewards::Pot<Test> as OnUnbalanced<_>>::on_unbalanced(slashed);
+
+		assert_eq!(Balances::reserved_balance_named(&id, &pot), 1_000);
+		assert_eq!(<rewards::Pot<Test> as RewardSource<_, _, _>>::held(), Some(1_000));
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn minted_rewards_raise_issuance() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let id = QuadraticVoting::reserve_id();
+		let issuance = Balances::total_issuance();
+
+		let paid = <rewards::Mint<Test> as RewardSource<_, _, _>>::pay(&alice, 1_000);
+
+		assert_eq!(paid, Ok(1_000));
+		assert_eq!(Balances::reserved_balance_named(&id, &alice), 1_000);
+		assert_eq!(Balances::total_issuance(), issuance + 1_000);
+		//nothing is held, so nothing is withdrawn
+		let withdrawn = <rewards::Mint<Test> as RewardSource<_, _, _>>::withdraw(1_000);
+		assert_eq!(withdrawn.peek(), 0);
+		assert_eq!(<rewards::Mint<Test> as RewardSource<_, _, _>>::held(), None);
+	});
+}
+
+#[test]
+fn slash_not_kept_by_pot_not_paid_out() {
+	new_test_ext().execute_with(|| {
+		SlashToPot::set(false);
+		let alice = get_alice();
+		let bob = get_bob();
+		for voter in [alice.clone(), bob.clone()] {
+			let origin = Origin::signed(voter);
+			let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+			let _ = QuadraticVoting::join_committee(origin);
+		}
+		let origin = Origin::signed(alice.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//alice outvotes bob
+		let mut reveals = Vec::new();
+		for (voter, vote, number) in [(&alice, Vote::Yes, 2), (&bob, Vote::No, 1)] {
+			let (commitment, salt) = generate(voter, proposal_hash, vote.clone(), number);
+			reveals.push((voter.clone(), vote, salt));
+			let _ = QuadraticVoting::commit_vote(
+				Origin::signed(voter.clone()),
+				proposal_hash,
+				commitment,
+				number,
+				Conviction::None,
+			);
+		}
+		run_to_block(101);
+		for (voter, vote, salt) in reveals {
+			let _ = QuadraticVoting::reveal_vote(Origin::signed(voter), proposal_hash, vote, salt);
+		}
+		run_to_block(151);
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(QuadraticVoting::claim(origin.clone(), proposal_hash, bob.clone()));
+		assert_ok!(QuadraticVoting::claim(origin, proposal_hash, alice.clone()));
+
+		let slash = EntryFee::get() / 10;
+		assert_eq!(Balances::reserved_balance(&bob), EntryFee::get() - slash);
+		//the slash was burned, so there is nothing to pay alice with
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get());
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance);
+		assert_eq!(Balances::total_issuance(), issuance - slash);
+		assert_eq!(<CarryOver<Test>>::get(), 0);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn minted_rewards_leave_nothing_in_pot() {
+	new_test_ext().execute_with(|| {
+		MintRewards::set(true);
+		let alice = get_alice();
+		let bob = get_bob();
+		for voter in [alice.clone(), bob.clone()] {
+			let origin = Origin::signed(voter);
+			let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+			let _ = QuadraticVoting::join_committee(origin);
+		}
+		let origin = Origin::signed(alice.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		//alice outvotes bob
+		let mut reveals = Vec::new();
+		for (voter, vote, number) in [(&alice, Vote::Yes, 2), (&bob, Vote::No, 1)] {
+			let (commitment, salt) = generate(voter, proposal_hash, vote.clone(), number);
+			reveals.push((voter.clone(), vote, salt));
+			let _ = QuadraticVoting::commit_vote(
+				Origin::signed(voter.clone()),
+				proposal_hash,
+				commitment,
+				number,
+				Conviction::None,
+			);
+		}
+		run_to_block(101);
+		for (voter, vote, salt) in reveals {
+			let _ = QuadraticVoting::reveal_vote(Origin::signed(voter), proposal_hash, vote, salt);
+		}
+		run_to_block(151);
+		let pot = QuadraticVoting::account_id();
+		let pot_balance = Balances::reserved_balance(&pot);
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(QuadraticVoting::claim(origin.clone(), proposal_hash, bob.clone()));
+		assert_ok!(QuadraticVoting::claim(origin, proposal_hash, alice.clone()));
+
+		let slash = EntryFee::get() / 10;
+		assert_eq!(Balances::reserved_balance(&bob), EntryFee::get() - slash);
+		//the reward is minted in place of the slash the pot would have kept
+		assert_eq!(Balances::reserved_balance(&alice), EntryFee::get() + slash);
+		assert_eq!(Balances::reserved_balance(&pot), pot_balance);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+#[test]
+fn keeper_paid_out_of_deposit() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+		KeeperBounty::set(30);
+
+		let alice = get_alice();
+		let charlie = get_charlie();
+		let origin = Origin::signed(alice.clone());
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let balance = Balances::free_balance(&charlie);
+
+		//anyone can close a due phase, for free
+		System::set_block_number(110);
+		let info = QuadraticVoting::close_vote(Origin::signed(charlie.clone()), proposal_hash);
+		assert_eq!(info.unwrap().pays_fee, Pays::No);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::KeeperPaid {
+			who: charlie.clone(),
+			proposal_hash,
+			bounty: 30,
+		}));
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().deposit, 70);
+
+		System::set_block_number(160);
+		let info = QuadraticVoting::close_reveal(Origin::signed(charlie.clone()), proposal_hash);
+		assert_eq!(info.unwrap().pays_fee, Pays::No);
+		assert_eq!(Balances::free_balance(&charlie), balance + 60);
+
+		//the rest of the deposit is slashed as nobody revealed
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::DepositSlashed {
+			proposal_hash,
+			who: alice,
+			amount: 40,
+		}));
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn keeper_paid_out_of_carry_over() {
+	new_test_ext().execute_with(|| {
+		KeeperBounty::set(30);
+		let pot = QuadraticVoting::account_id();
+		let _ = Balances::set_balance(Origin::root(), pot.clone(), 1_000, 0);
+		assert_ok!(Balances::reserve_named(&QuadraticVoting::reserve_id(), &pot, 50));
+		<CarryOver<Test>>::put(50);
+
+		let alice = get_alice();
+		let charlie = get_charlie();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let balance = Balances::free_balance(&charlie);
+
+		//the track takes no deposit
+		System::set_block_number(110);
+		assert_ok!(QuadraticVoting::close_vote(Origin::signed(charlie.clone()), proposal_hash));
+		assert_eq!(<CarryOver<Test>>::get(), 20);
+		assert_ok!(QuadraticVoting::check_pot());
+
+		//only what is carried over is paid
+		System::set_block_number(160);
+		assert_ok!(QuadraticVoting::close_reveal(Origin::signed(charlie.clone()), proposal_hash));
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::KeeperPaid {
+			who: charlie.clone(),
+			proposal_hash,
+			bounty: 20,
+		}));
+		assert_eq!(Balances::free_balance(&charlie), balance + 50);
+		assert_eq!(<CarryOver<Test>>::get(), 0);
+		assert_ok!(QuadraticVoting::check_pot());
+	});
+}
+
+#[test]
+fn premature_close_pays_fee() {
+	new_test_ext().execute_with(|| {
+		Tracks::set(vec![(0, TrackInfo { deposit: 100, ..general_track() })]);
+		KeeperBounty::set(30);
+
+		let alice = get_alice();
+		let charlie = get_charlie();
+		let origin = Origin::signed(alice);
+		let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin,
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let balance = Balances::free_balance(&charlie);
+
+		System::set_block_number(50);
+		let err = QuadraticVoting::close_vote(Origin::signed(charlie.clone()), proposal_hash)
+			.unwrap_err();
+		assert_eq!(err.error, Error::<Test>::TooEarly.into());
+		assert_eq!(err.post_info.pays_fee, Pays::Yes);
+
+		System::set_block_number(110);
+		assert_ok!(QuadraticVoting::close_vote(Origin::signed(charlie.clone()), proposal_hash));
+		let err = QuadraticVoting::close_reveal(Origin::signed(charlie.clone()), proposal_hash)
+			.unwrap_err();
+		assert_eq!(err.error, Error::<Test>::TooEarly.into());
+		assert_eq!(err.post_info.pays_fee, Pays::Yes);
+
+		//only the due phase was paid for
+		assert_eq!(Balances::free_balance(&charlie), balance + 30);
+	});
+}
+
+#[test]
+fn phases_close_early_once_everyone_acted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let alice = get_alice();
+		let bob = get_bob();
+		let origin_alice = Origin::signed(alice.clone());
+		let origin_bob = Origin::signed(bob.clone());
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+
+		let (commitment, salt) = generate(&alice, proposal_hash, Vote::Yes, 2);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			2,
+			Conviction::None,
+		);
+		assert_noop!(
+			QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash),
+			Error::<Test>::TooEarly
+		);
+
+		let (commitment, _) = generate(&bob, proposal_hash, Vote::No, 1);
+		let _ = QuadraticVoting::commit_vote(
+			origin_bob.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::CommitComplete(
+			proposal_hash,
+		)));
+		assert_ok!(QuadraticVoting::close_vote(origin_alice.clone(), proposal_hash));
+		//the reveal phase starts now rather than at the end of the commit phase
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().reveal_end, Some(51));
+		assert!(!<Schedule<Test>>::contains_key(101));
+
+		let _ = QuadraticVoting::reveal_vote(origin_alice.clone(), proposal_hash, Vote::Yes, salt);
+		assert_noop!(
+			QuadraticVoting::close_reveal(origin_alice.clone(), proposal_hash),
+			Error::<Test>::TooEarly
+		);
+		let _ = QuadraticVoting::reveal_vote(origin_bob, proposal_hash, Vote::No, salt);
+		System::assert_has_event(Event::QuadraticVoting(crate::Event::RevealComplete(
+			proposal_hash,
+		)));
+		assert_ok!(QuadraticVoting::close_reveal(origin_alice, proposal_hash));
+
+		let archived = <Archive<Test>>::get(proposal_hash, 1).unwrap();
+		assert_eq!(archived.outcome, Outcome::Approved);
+		assert!(!<Schedule<Test>>::contains_key(51));
+	});
+}
+
+#[test]
+fn late_joiners_not_waited_for() {
+	new_test_ext().execute_with(|| {
+		let alice = get_alice();
+		let bob = get_bob();
+		let charlie = get_charlie();
+		let origin_alice = Origin::signed(alice.clone());
+		let origin_bob = Origin::signed(bob.clone());
+		let origin_charlie = Origin::signed(charlie.clone());
+		let _ = Balances::set_balance(Origin::root(), charlie.clone(), 1_000_000 * UNIT, 0);
+		let _ = Identity::set_identity(origin_alice.clone(), Box::new(data()));
+		let _ = Identity::set_identity(origin_bob.clone(), Box::new(data()));
+		let _ = Identity::set_identity(origin_charlie.clone(), Box::new(data()));
+		let _ = QuadraticVoting::join_committee(origin_alice.clone());
+		let _ = QuadraticVoting::join_committee(origin_bob.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let _ = QuadraticVoting::join_committee(origin_charlie.clone());
+
+		//bob delegates, so the commit of alice carries both
+		let _ = QuadraticVoting::delegate(origin_bob, alice.clone(), None);
+		let (commitment, _) = generate(&charlie, proposal_hash, Vote::No, 1);
+		let _ = QuadraticVoting::commit_vote(
+			origin_charlie,
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		);
+		assert_eq!(<ProposalData<Test>>::get(proposal_hash).unwrap().turnout, 0);
+
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 1);
+		let _ = QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		);
+		let proposal = <ProposalData<Test>>::get(proposal_hash).unwrap();
+		assert_eq!((proposal.electorate, proposal.turnout), (2, 2));
+		assert_eq!(proposal.unrevealed, 2);
+		assert_ok!(QuadraticVoting::close_vote(origin_alice, proposal_hash));
+		assert_eq!(<Turnout<Test>>::iter_prefix(proposal_hash).count(), 0);
+	});
+}
+
+#[test]
+fn electorate_excludes_departed_and_suspended_members() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let alice = get_alice();
+		let bob = get_bob();
+		let charlie = get_charlie();
+		let dave = get_account_id_from_seed::<sp_core::sr25519::Public>("Dave");
+		for member in [&alice, &bob, &charlie, &dave] {
+			let origin = Origin::signed(member.clone());
+			let _ = Balances::set_balance(Origin::root(), member.clone(), 1_000_000 * UNIT, 0);
+			let _ = Identity::set_identity(origin.clone(), Box::new(data()));
+			let _ = QuadraticVoting::join_committee(origin);
+		}
+		let origin_alice = Origin::signed(alice.clone());
+		let origin_bob = Origin::signed(bob.clone());
+
+		//dave is suspended before the proposal is created
+		let _ = Identity::clear_identity(Origin::signed(dave.clone()));
+		let _ = QuadraticVoting::refresh_identity(origin_alice.clone(), dave.clone());
+		let _ = QuadraticVoting::create_proposal(
+			origin_alice.clone(),
+			Box::new(Data::Raw(BoundedVec::default())),
+			0,
+			Threshold::SimpleMajority,
+			100,
+		);
+		let proposal_hash = <Proposals<Test>>::get()[0];
+		let electorate = || <ProposalData<Test>>::get(proposal_hash).unwrap().electorate;
+		assert_eq!(electorate(), 3);
+
+		assert_ok!(QuadraticVoting::leave_committee(Origin::signed(charlie)));
+		assert_eq!(electorate(), 2);
+		//dave was never counted
+		assert_ok!(QuadraticVoting::kick_member(Origin::root(), dave, Perbill::zero()));
+		assert_eq!(electorate(), 2);
+		let _ = Identity::clear_identity(origin_bob.clone());
+		assert_ok!(QuadraticVoting::refresh_identity(origin_alice.clone(), bob.clone()));
+		assert_eq!(electorate(), 1);
+
+		//alice is the only member left to wait for
+		let (commitment, _) = generate(&alice, proposal_hash, Vote::Yes, 1);
+		assert_ok!(QuadraticVoting::commit_vote(
+			origin_alice.clone(),
+			proposal_hash,
+			commitment,
+			1,
+			Conviction::None,
+		));
+		System::assert_last_event(Event::QuadraticVoting(crate::Event::CommitComplete(
+			proposal_hash,
+		)));
+
+		//a reinstated member is waited for again
+		let _ = Identity::set_identity(origin_bob, Box::new(data()));
+		assert_ok!(QuadraticVoting::refresh_identity(origin_alice, bob));
+		assert_eq!(electorate(), 2);
+	});
+}
+
 fn data() -> IdentityInfo<MaxAdditionalFields> {
 	IdentityInfo {
 		display: pallet_identity::Data::Raw(b"ten".to_vec().try_into().unwrap()),
@@ -2,67 +2,511 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{traits::ConstU32, BoundedVec};
 
 use frame_support::sp_runtime::RuntimeDebug;
+use sp_runtime::{DispatchResult, Perbill};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::prelude::*;
 
 pub type VoteToken = u8;
+/// The index of an option of a multi-option proposal
+pub type OptionIndex = u8;
+/// The maximum number of options of a multi-option proposal
+pub type MaxOptions = ConstU32<16>;
+/// The options of a multi-option proposal
+pub type Options = BoundedVec<Data, MaxOptions>;
+/// The voting tokens given to each option of a multi-option proposal
+pub type Choices = BoundedVec<(OptionIndex, VoteToken), MaxOptions>;
+/// The id of a proposal track
+pub type TrackId = u16;
 
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Data {
 	/// The data is stored directly.
 	Raw(BoundedVec<u8, ConstU32<2048>>),
 }
 
-#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Proposal<AccountId, BlockNumber, Balance> {
 	/// The title of proposal
 	pub title: Data,
 	/// Who proposed
 	pub proposer: AccountId,
+	/// The track the proposal follows
+	pub track: TrackId,
+	/// The amount reserved from the proposer
+	pub deposit: Balance,
 	/// Total votes for proposal to pass
 	pub ayes: u32,
 	/// Total votes for proposal to get rejected
 	pub nays: u32,
+	/// Total votes of voters who abstained
+	pub abstains: u32,
+	/// The share of votes the proposal needs to pass
+	pub threshold: Threshold,
+	/// The options to pick from, empty if the proposal is voted yes or no
+	pub options: Options,
+	/// Total votes for each option
+	pub tallies: Vec<u32>,
 	/// The hard end of voting phase
 	pub poll_end: BlockNumber,
+	/// The length of the voting phase, the unit of conviction locks
+	pub length: BlockNumber,
 	/// The hard end of reveal phase
 	pub reveal_end: Option<BlockNumber>,
 	/// The number of votes each voter gave
 	pub votes: Vec<(AccountId, u8, Vote)>,
+	/// The voters who revealed a vote with conviction
+	pub convictions: Vec<(AccountId, Conviction)>,
 	/// Users who revealed their choices.
 	/// Allows to verify who did not reveal on time.
 	/// This may look as data duplication, but it will reduce runtime
 	/// otherwise we need to parse `votes` vector and compose vector of required format
 	pub revealed: Vec<AccountId>,
-	/// The amount that was slashed and distributed
+	/// Users who committed a vote.
+	/// Those missing from `revealed` at the end of reveal phase are penalised
+	pub committed: Vec<AccountId>,
+	/// The number of members whose voting tokens were spent on the proposal, delegators included
+	pub voters: u32,
+	/// The members eligible to commit on the proposal and not suspended, counted when it was
+	/// created and lowered as they leave, are kicked or suspended before committing
+	pub electorate: u32,
+	/// The number of members in the committee when the proposal was created,
+	/// the base of a quorum by members
+	pub members: u32,
+	/// The join index of the last member eligible to vote, see [`crate::JoinIndex`].
+	/// Members who joined later can vote but are not waited for
+	pub last_eligible: u32,
+	/// The eligible members whose voting tokens were committed, delegators included
+	pub turnout: u32,
+	/// The commits not revealed yet
+	pub unrevealed: u32,
+}
+
+impl<AccountId, BlockNumber, Balance> Proposal<AccountId, BlockNumber, Balance> {
+	/// Whether the voters pick among the options of the proposal rather than yes or no
+	pub fn is_multi_option(&self) -> bool {
+		!self.options.is_empty()
+	}
+
+	/// The voting tokens spent by a vote of the given number.
+	/// The number committed to a multi-option proposal is already the sum of squares
+	pub fn cost(&self, number: VoteToken) -> VoteToken {
+		if self.is_multi_option() {
+			number
+		} else {
+			number.saturating_pow(2)
+		}
+	}
+
+	/// Whether every eligible member has committed, so the commit phase can be closed early
+	pub fn all_committed(&self) -> bool {
+		self.turnout >= self.electorate
+	}
+
+	/// Whether votes were committed and every commit has been revealed,
+	/// so the reveal phase can be closed early
+	pub fn all_revealed(&self) -> bool {
+		!self.committed.is_empty() && self.unrevealed == 0
+	}
+
+	/// The number of votes a delegate casts for a delegator with the given voting tokens.
+	/// The delegator follows the delegate up to the number it can afford,
+	/// the choices of a multi-option vote are only followed in full
+	pub fn delegated_number(&self, number: VoteToken, tokens: VoteToken) -> VoteToken {
+		if self.is_multi_option() {
+			return if tokens >= number { number } else { 0 }
+		}
+		let mut affordable: VoteToken = 0;
+		while affordable < number && (affordable as u16 + 1).pow(2) <= tokens as u16 {
+			affordable += 1;
+		}
+		affordable
+	}
+}
+
+impl<AccountId: Clone + PartialEq, BlockNumber, Balance> Proposal<AccountId, BlockNumber, Balance> {
+	/// The conviction the voter revealed the vote with
+	pub fn conviction_of(&self, who: &AccountId) -> Conviction {
+		self.convictions
+			.iter()
+			.find(|(account, _)| account == who)
+			.map_or(Conviction::None, |(_, conviction)| *conviction)
+	}
+
+	/// Adds the revealed vote to the tallies, multiplied by the conviction, and records the voter.
+	/// The vote must fit the proposal
+	pub fn count(&mut self, who: AccountId, number: VoteToken, vote: Vote, conviction: Conviction) {
+		let multiplier = conviction.multiplier();
+		match &vote {
+			Vote::Yes => self.ayes += number as u32 * multiplier,
+			Vote::No => self.nays += number as u32 * multiplier,
+			Vote::Abstain => self.abstains += number as u32 * multiplier,
+			Vote::Options(choices) =>
+				for (option, tokens) in choices.iter() {
+					if let Some(tally) = self.tallies.get_mut(*option as usize) {
+						*tally += *tokens as u32 * multiplier;
+					}
+				},
+		}
+		if conviction != Conviction::None {
+			self.convictions.push((who.clone(), conviction));
+		}
+		//push the vote counters
+		self.votes.push((who.clone(), number, vote));
+		//update the list of voters that revealed their choices
+		self.revealed.push(who);
+	}
+
+	/// Removes the commitment and the vote of the account as if it never took part
+	pub fn withdraw_vote(&mut self, who: &AccountId) {
+		let multiplier = self.conviction_of(who).multiplier();
+		if let Some(index) = self.votes.iter().position(|(account, _, _)| account == who) {
+			let (_, number, vote) = self.votes.remove(index);
+			let number = number as u32 * multiplier;
+			match vote {
+				Vote::Yes => self.ayes = self.ayes.saturating_sub(number),
+				Vote::No => self.nays = self.nays.saturating_sub(number),
+				Vote::Abstain => self.abstains = self.abstains.saturating_sub(number),
+				Vote::Options(choices) =>
+					for (option, tokens) in choices.iter() {
+						if let Some(tally) = self.tallies.get_mut(*option as usize) {
+							*tally = tally.saturating_sub(*tokens as u32 * multiplier);
+						}
+					},
+			}
+		}
+		if self.committed.contains(who) && !self.revealed.contains(who) {
+			self.unrevealed = self.unrevealed.saturating_sub(1);
+		}
+		self.convictions.retain(|(account, _)| account != who);
+		self.revealed.retain(|account| account != who);
+		self.committed.retain(|account| account != who);
+	}
+}
+
+/// How long a voter locks the stake after the proposal closes in exchange for a heavier vote.
+/// The lock is a multiple of the length of the commit phase of the proposal
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+	/// The vote counts once and nothing is locked
+	None,
+	/// The vote counts twice, the stake is locked for one proposal length
+	Locked1x,
+	/// The vote counts three times, the stake is locked for two proposal lengths
+	Locked2x,
+	/// The vote counts four times, the stake is locked for four proposal lengths
+	Locked4x,
+	/// The vote counts five times, the stake is locked for eight proposal lengths
+	Locked8x,
+}
+
+impl Conviction {
+	/// The factor applied to the votes in the tally
+	pub fn multiplier(&self) -> u32 {
+		match self {
+			Conviction::None => 1,
+			Conviction::Locked1x => 2,
+			Conviction::Locked2x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked8x => 5,
+		}
+	}
+
+	/// The number of proposal lengths the stake is locked for after the close
+	pub fn lock_periods(&self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked4x => 4,
+			Conviction::Locked8x => 8,
+		}
+	}
+}
+
+/// The final outcome of a proposal
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Outcome {
+	/// More votes for the proposal
+	Approved,
+	/// More votes against the proposal
+	Disapproved,
+	/// No consensus has been reached
+	Tie,
+	/// The option of a multi-option proposal with the most votes
+	Chosen(OptionIndex),
+	/// Too few votes were revealed to decide
+	NoQuorum,
+	/// More votes for the proposal or its leading option, but short of its threshold.
+	/// The proposal is rejected without slashing either side
+	Rejected,
+	/// The proposal was cancelled before it was decided
+	Cancelled,
+}
+
+/// The quality of an identity as judged by the registrars, from worst to best
+#[derive(
+	Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, Encode, Decode, TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum IdentityLevel {
+	/// No identity or one judged erroneous
+	None,
+	/// An identity without a positive judgement
+	Unverified,
+	/// Judged reasonable by a registrar
+	Reasonable,
+	/// Judged known good by a registrar
+	KnownGood,
+}
+
+/// The share of votes a proposal needs to pass
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Threshold {
+	/// More votes for than against.
+	/// The option of a multi-option proposal only needs the most votes
+	SimpleMajority,
+	/// At least two thirds of the votes
+	SuperMajority,
+	/// No votes against
+	Unanimity,
+}
+
+impl Threshold {
+	/// Whether the votes for reach the threshold
+	pub fn approves(&self, ayes: u32, nays: u32) -> bool {
+		let (ayes, nays) = (ayes as u64, nays as u64);
+		match self {
+			Threshold::SimpleMajority => ayes > nays,
+			Threshold::SuperMajority => ayes > nays && ayes * 3 >= (ayes + nays) * 2,
+			Threshold::Unanimity => ayes > 0 && nays == 0,
+		}
+	}
+}
+
+/// The minimum turnout for the results of a proposal to count
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Quorum {
+	/// The revealed votes, abstentions included
+	Votes(u32),
+	/// The share of committee members who revealed
+	Members(Perbill),
+}
+
+/// Compact record of the results of a closed proposal
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ArchivedProposal<AccountId, BlockNumber, Balance> {
+	/// Who proposed
+	pub proposer: AccountId,
+	/// The track the proposal followed
+	pub track: TrackId,
+	/// The final outcome
+	pub outcome: Outcome,
+	/// Total votes for proposal to pass
+	pub ayes: u32,
+	/// Total votes for proposal to get rejected
+	pub nays: u32,
+	/// Total votes of voters who abstained
+	pub abstains: u32,
+	/// Total votes for each option of a multi-option proposal
+	pub tallies: Vec<u32>,
+	/// The number of voters who revealed their choices
+	pub revealed: u32,
+	/// The amount that was slashed and distributed, known once every voter has claimed
 	pub payout: Balance,
-	/// Is proposal closed
-	pub closed: bool
+	/// The block the proposal was closed at
+	pub closed_at: BlockNumber,
+	/// The result of dispatching the call of an approved proposal
+	pub executed: Option<DispatchResult>,
+}
+
+/// A revealed vote on a closed proposal waiting to be claimed
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Claim<AccountId> {
+	/// The voter
+	pub who: AccountId,
+	/// The number of votes given, the voting tokens spent on it are refunded
+	pub number: VoteToken,
+	/// The votes given to the losing side, the voter is slashed for them
+	pub lost: Option<VoteToken>,
+	/// The conviction the vote counted with, it locks the stake once claimed
+	pub conviction: Conviction,
+}
+
+/// What the voters of a closed proposal have left to claim.
+/// Removed once everyone has claimed
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Settlement<AccountId, BlockNumber, Balance> {
+	/// The difference between the tallies of both sides, passed to the slashing policy
+	pub margin: u32,
+	/// The block the proposal was closed at
+	pub closed_at: BlockNumber,
+	/// The length of the voting phase, the unit of conviction locks
+	pub length: BlockNumber,
+	/// Whether the number of a vote is already its cost, see [`Proposal::cost`]
+	pub multi_option: bool,
+	/// The revealed votes not claimed yet
+	pub votes: Vec<Claim<AccountId>>,
+	/// Members who committed, did not reveal and have not been slashed yet
+	pub unrevealed: Vec<AccountId>,
+	/// The accounts paid out of the pool with their shares, not claimed yet
+	pub winners: Vec<(AccountId, Balance)>,
+	/// The shares of every winner together
+	pub shares: Balance,
+	/// The amount slashed so far
+	pub pool: Balance,
+}
+
+impl<AccountId, BlockNumber, Balance> Settlement<AccountId, BlockNumber, Balance> {
+	/// The voting tokens spent by a vote of the given number
+	pub fn cost(&self, number: VoteToken) -> VoteToken {
+		if self.multi_option {
+			number
+		} else {
+			number.saturating_pow(2)
+		}
+	}
+
+	/// Whether the losing side or the non-revealers still have to be slashed.
+	/// The winners are paid once the pool is complete
+	pub fn slashes_pending(&self) -> bool {
+		!self.unrevealed.is_empty() || self.votes.iter().any(|claim| claim.lost.is_some())
+	}
+
+	/// Whether everyone has claimed
+	pub fn is_settled(&self) -> bool {
+		self.votes.is_empty() && self.unrevealed.is_empty() && self.winners.is_empty()
+	}
+}
+
+/// The parameters shared by the proposals of a track
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct TrackInfo<Balance, BlockNumber> {
+	/// The shortest commit phase
+	pub min_length: BlockNumber,
+	/// The longest commit phase
+	pub max_length: BlockNumber,
+	/// The length of reveal phase
+	pub reveal_length: BlockNumber,
+	/// The amount reserved from the proposer until the proposal is closed
+	pub deposit: Balance,
+	/// The turnout below which the results do not count
+	pub quorum: Quorum,
+	/// The thresholds a proposer may pick from
+	pub thresholds: Vec<Threshold>,
+	/// Maximum number of proposals of the track active in parallel
+	pub max_proposals: u32,
+}
+
+/// Where the funds of the pot that no proposal pays out go: the rounding dust of rewards,
+/// the slashes nobody is rewarded with, late reveals, forfeited stakes and spam deposits
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum Leftover<AccountId> {
+	/// Carried over to the reward of the next proposal with winners
+	RollOver,
+	/// Moved to the free balance of the account, e.g. a treasury
+	Treasury(AccountId),
+	/// Burned
+	Burn,
+}
+
+/// Phase transition of a proposal scheduled for a block
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum Transition<Hash> {
+	/// Close the commit phase and start the reveal phase
+	CloseVote(Hash),
+	/// Close the reveal phase and announce the results
+	CloseReveal(Hash),
+}
+
+/// The phase a proposal is in
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Phase<BlockNumber> {
+	/// Votes are committed until the block
+	Commit { ends: BlockNumber },
+	/// Votes are revealed until the block
+	Reveal { ends: BlockNumber },
+	/// The proposal was closed and archived at the block
+	Closed { at: BlockNumber },
 }
 
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Vote {
 	Yes,
 	No,
+	/// The voting tokens given to the options of a multi-option proposal.
+	/// The cost of the vote is the sum of squares of the tokens
+	Options(Choices),
+	/// Takes part without picking a side, the voter is neither rewarded nor slashed
+	Abstain,
 }
 
 
+/// The versioned preimage of a vote commitment.
+/// The index of the variant is the version of the format
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
-pub struct Commit<Signature> {
-	/// The signed choice of a voter
-	pub signature: Signature,
-	/// The number of votes the voter gives to their choice.
+pub enum CommitPayload<AccountId, Hash> {
+	/// Binds the vote to the pallet instance, the chain, the proposal and the voter
+	V1 {
+		/// The id of the pallet instance
+		domain: [u8; 8],
+		/// The genesis hash of the chain
+		genesis_hash: Hash,
+		/// The proposal voted on
+		proposal_hash: Hash,
+		/// The committed weight of the vote
+		number: VoteToken,
+		/// The account of the voter
+		voter: AccountId,
+		/// The choice of the voter
+		vote: Vote,
+		/// The secret salt of the voter
+		salt: [u8; 32],
+	},
+}
+
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Commit<Hash> {
+	/// The hash of the [`CommitPayload`].
+	/// Hides the choice of a voter until the reveal phase
+	pub commitment: Hash,
+	/// The number of votes the voter gives to their choice,
+	/// or the voting tokens spent on a multi-option proposal.
 	/// Must be exposed and unencrypted to allow double spend of votes
 	pub number: u8,
-	/// Salt which comes with the choice to ensure the security
-	pub salt: u32,
+	/// The conviction the vote counts with once revealed
+	pub conviction: Conviction,
+}
+
+/// The record kept for every member of the committee
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, Default)]
+pub struct MemberInfo<Balance> {
+	/// The voting tokens left to the member
+	pub voting_tokens: VoteToken,
+	/// The committee stake held under the reserve of the pallet
+	pub reserved_balance: Balance,
 }
 
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct VoterBalance<Balance> {
 	/// The number of votes the voter gives to their choice.
 	/// Must be exposed and unencrypted to allow double spend of votes
 	pub voting_tokens: VoteToken,
 	/// Salt which comes with the choice to ensure the security
 	pub reserved_balance: Balance,
+	/// The voting tokens of the members delegating to the voter
+	pub delegated_in: u32,
+	/// The voting tokens of the voter if it delegates on any track
+	pub delegated_out: VoteToken,
 }
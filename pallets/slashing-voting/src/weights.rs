@@ -0,0 +1,297 @@
+//! Weights for pallet_slashing_voting
+//!
+//! HAND-WRITTEN ESTIMATES, NOT BENCHMARK OUTPUT. The values below were derived from the storage
+//! accesses of each call and the benchmark components and have never been measured.
+//! They must be replaced with the output of the benchmarks on reference hardware
+//! before the runtime goes to production:
+//!
+//! cargo build --release --features runtime-benchmarks
+//! ./target/release/voting-node benchmark pallet \
+//!     --chain dev \
+//!     --pallet pallet_slashing_voting \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --execution wasm \
+//!     --wasm-execution compiled \
+//!     --template ./.maintain/frame-weight-template.hbs \
+//!     --output ./pallets/slashing-voting/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_slashing_voting.
+pub trait WeightInfo {
+	fn join_committee() -> Weight;
+	fn leave_committee() -> Weight;
+	fn create_proposal(p: u32, ) -> Weight;
+	fn create_call_proposal(p: u32, ) -> Weight;
+	fn create_multi_option_proposal(p: u32, ) -> Weight;
+	fn commit_vote(c: u32, d: u32, ) -> Weight;
+	fn reveal_vote(r: u32, d: u32, ) -> Weight;
+	fn close_vote(t: u32, ) -> Weight;
+	fn close_reveal(r: u32, ) -> Weight;
+	fn prune_archive() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn force_cancel(c: u32, ) -> Weight;
+	fn force_add_member() -> Weight;
+	fn kick_member(p: u32, ) -> Weight;
+	fn ban_member(p: u32, ) -> Weight;
+	fn refresh_identity() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn claim(v: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_slashing_voting, to be replaced by the benchmark output.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn join_committee() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn leave_committee() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn create_proposal(p: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn create_call_proposal(p: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn create_multi_option_proposal(p: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote(c: u32, d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((60_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(d as Weight)))
+	}
+	fn reveal_vote(r: u32, d: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn close_vote(t: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((1_500_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	fn close_reveal(r: u32, ) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	fn prune_archive() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_proposal() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn force_cancel(c: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((8_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	fn force_add_member() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn kick_member(p: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	fn ban_member(p: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	fn refresh_identity() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn delegate() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn claim(v: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn join_committee() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn leave_committee() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn create_proposal(p: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn create_call_proposal(p: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_multi_option_proposal(p: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote(c: u32, d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((60_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(d as Weight)))
+	}
+	fn reveal_vote(r: u32, d: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn close_vote(t: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((1_500_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	fn close_reveal(r: u32, ) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn prune_archive() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_proposal() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn force_cancel(c: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((8_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	fn force_add_member() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn kick_member(p: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	fn ban_member(p: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	fn refresh_identity() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn delegate() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn claim(v: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+}
@@ -23,7 +23,10 @@ use sp_std::prelude::*;
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
+use frame_support::traits::EitherOfDiverse;
 use frame_system::EnsureRoot;
+use pallet_identity::Judgement;
+use pallet_slashing_voting::types::{IdentityLevel, Leftover, Quorum, Threshold, TrackId, TrackInfo};
 
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
@@ -106,10 +109,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -241,7 +244,7 @@ impl pallet_timestamp::Config for Runtime {
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -323,32 +326,134 @@ impl pallet_identity::Config for Runtime {
 
 pub struct VotingIdentityProvider;
 impl pallet_slashing_voting::IdentityProvider<AccountId> for VotingIdentityProvider {
-	fn check_existence(account: &AccountId) -> bool {
-		Identity::identity(account).is_some()
+	fn judgement(account: &AccountId) -> IdentityLevel {
+		let registration = match Identity::identity(account) {
+			Some(registration) => registration,
+			None => return IdentityLevel::None,
+		};
+		let judgements = registration.judgements;
+		if judgements.iter().any(|(_, judgement)| *judgement == Judgement::Erroneous) {
+			return IdentityLevel::None
+		}
+		judgements
+			.iter()
+			.map(|(_, judgement)| match judgement {
+				Judgement::KnownGood => IdentityLevel::KnownGood,
+				Judgement::Reasonable => IdentityLevel::Reasonable,
+				_ => IdentityLevel::Unverified,
+			})
+			.max()
+			.unwrap_or(IdentityLevel::Unverified)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_identity(account: &AccountId) {
+		let info = pallet_identity::IdentityInfo {
+			display: pallet_identity::Data::Raw(b"ten".to_vec().try_into().unwrap()),
+			additional: Default::default(),
+			legal: Default::default(),
+			web: Default::default(),
+			riot: Default::default(),
+			twitter: Default::default(),
+			email: Default::default(),
+			pgp_fingerprint: Default::default(),
+			image: Default::default(),
+		};
+		let _ = Identity::set_identity(Origin::signed(account.clone()), Box::new(info));
+
+		//a registrar has to vouch for the identity
+		let registrar = AccountId::new([0u8; 32]);
+		if Identity::registrars().is_empty() {
+			let _ = Identity::add_registrar(Origin::root(), registrar.clone());
+		}
+		let _ = Identity::provide_judgement(
+			Origin::signed(registrar),
+			0,
+			account.clone().into(),
+			Judgement::Reasonable,
+		);
 	}
 }
 
 parameter_types! {
 	pub const EntryFee: Balance = 30_000 * UNIT;
 	pub const MaxProposals: u32 = 10u32;
-	pub const RevealLength: BlockNumber = 7u32;
-	pub const MinLength: BlockNumber = 15u32;
 	pub const MaxTokens: u8 = 100u8;
+	pub const MaxDelegators: u32 = 32;
+	pub const MaxVotersPerProposal: u32 = 256;
 	pub const VotingPalletId: PalletId = PalletId(*b"p/v8t1ng");
+	/// Approved proposals may dispatch calls of up to a half of the block
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
+	/// The proposals due at a block are closed in up to 60% of it, the rest in the next blocks
+	pub MaxTransitionWeight: Weight = Perbill::from_percent(60) * BlockWeights::get().max_block;
+	pub const ArchiveRetention: BlockNumber = 7 * DAYS;
+	pub const NonRevealPenalty: Perbill = Perbill::from_percent(20);
+	pub const RefundNonRevealed: bool = true;
+	/// There is no treasury, the dust of the pot is paid out with the next rewards
+	pub const VotingLeftover: Leftover<AccountId> = Leftover::RollOver;
+	/// Closing both phases of a proposal takes a fifth of the smallest deposit
+	pub const KeeperBounty: Balance = UNIT;
+	/// Self-set identities are not enough, a registrar has to vouch for the members
+	pub const MinJudgement: IdentityLevel = IdentityLevel::Reasonable;
+	pub VotingTracks: Vec<(TrackId, TrackInfo<Balance, BlockNumber>)> = vec![
+		// signals and other text proposals
+		(0, TrackInfo {
+			min_length: 15,
+			max_length: 7 * DAYS,
+			reveal_length: 7,
+			deposit: 10 * UNIT,
+			quorum: Quorum::Members(Perbill::from_percent(20)),
+			thresholds: vec![
+				Threshold::SimpleMajority,
+				Threshold::SuperMajority,
+				Threshold::Unanimity,
+			],
+			max_proposals: 10,
+		}),
+		// proposals dispatching privileged calls
+		(1, TrackInfo {
+			min_length: DAYS,
+			max_length: 14 * DAYS,
+			reveal_length: DAYS,
+			deposit: 1_000 * UNIT,
+			quorum: Quorum::Members(Perbill::from_percent(50)),
+			thresholds: vec![Threshold::SuperMajority, Threshold::Unanimity],
+			max_proposals: 2,
+		}),
+	];
 }
 
+/// Root or a proposal approved by the voting committee
+type RootOrCommittee =
+	EitherOfDiverse<EnsureRoot<AccountId>, pallet_slashing_voting::EnsureApproved<Runtime>>;
+
 impl pallet_slashing_voting::Config for Runtime {
 	type Event = Event;
 	type IdentityProvider = VotingIdentityProvider;
+	type MinJudgement = MinJudgement;
 	type Currency = Balances;
 	type BasicDeposit = EntryFee;
 	type MaxProposals = MaxProposals;
-	type Public = <Signature as Verify>::Signer;
-	type Signature = MultiSignature;
-	type RevealLength = RevealLength;
-	type MinLength = MinLength;
+	type Tracks = VotingTracks;
 	type MaxVotingTokens = MaxTokens;
+	type MaxDelegators = MaxDelegators;
+	type MaxVotersPerProposal = MaxVotersPerProposal;
 	type PalletId = VotingPalletId;
+	type Origin = Origin;
+	type Proposal = Call;
+	type CancelOrigin = RootOrCommittee;
+	type MembershipOrigin = RootOrCommittee;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxTransitionWeight = MaxTransitionWeight;
+	type ArchiveRetention = ArchiveRetention;
+	type NonRevealPenalty = NonRevealPenalty;
+	type RefundNonRevealed = RefundNonRevealed;
+	type SlashingPolicy = pallet_slashing_voting::policy::DefaultPolicy;
+	type Leftover = VotingLeftover;
+	type Slash = pallet_slashing_voting::rewards::Pot<Runtime>;
+	type RewardSource = pallet_slashing_voting::rewards::Pot<Runtime>;
+	type KeeperBounty = KeeperBounty;
+	type WeightInfo = pallet_slashing_voting::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -393,6 +498,14 @@ pub type SignedExtra = (
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
+/// Storage migrations applied on runtime upgrade.
+pub type Migrations = (
+	pallet_slashing_voting::migrations::v1::MigrateToV1<Runtime>,
+	pallet_slashing_voting::migrations::v2::MigrateToV2<Runtime, Signature>,
+	pallet_slashing_voting::migrations::v3::MigrateToV3<Runtime>,
+	pallet_slashing_voting::migrations::v4::MigrateToV4<Runtime>,
+	pallet_slashing_voting::migrations::v5::MigrateToV5<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -400,6 +513,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -413,6 +527,7 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
+		[pallet_slashing_voting, QuadraticVoting]
 	);
 }
 
@@ -547,6 +662,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_slashing_voting_runtime_api::SlashingVotingApi<Block, AccountId, BlockNumber, Balance, Hash>
+		for Runtime
+	{
+		fn proposal(hash: Hash) -> Option<pallet_slashing_voting::ProposalOf<Runtime>> {
+			pallet_slashing_voting::ProposalData::<Runtime>::get(hash)
+		}
+
+		fn active_proposals() -> Vec<Hash> {
+			pallet_slashing_voting::Proposals::<Runtime>::get().into_inner()
+		}
+
+		fn archived(
+			hash: Hash,
+		) -> Vec<pallet_slashing_voting::types::ArchivedProposal<AccountId, BlockNumber, Balance>> {
+			QuadraticVoting::archived(&hash)
+		}
+
+		fn member(account: AccountId) -> Option<pallet_slashing_voting::types::VoterBalance<Balance>> {
+			QuadraticVoting::member(&account)
+		}
+
+		fn phase(hash: Hash) -> Option<pallet_slashing_voting::types::Phase<BlockNumber>> {
+			QuadraticVoting::phase(&hash)
+		}
+
+		fn pending_reveals(account: AccountId) -> Vec<Hash> {
+			QuadraticVoting::pending_reveals(&account)
+		}
+
+		fn locked_until(account: AccountId) -> Option<BlockNumber> {
+			QuadraticVoting::locked_until(&account)
+		}
+
+		fn pending_claims(account: AccountId) -> Vec<Hash> {
+			QuadraticVoting::pending_claims(&account)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (